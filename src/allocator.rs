@@ -0,0 +1,166 @@
+//! Allocation-related types shared by the data structures in this crate.
+
+use core::{alloc::Layout, fmt};
+
+pub use implementation::{Allocator, Global};
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use super::AllocError;
+    use core::{alloc::Layout, ptr::NonNull};
+
+    /// A source of memory that data structures can be placed in (e.g. the global heap, or a bump arena).
+    ///
+    /// This is a small stand-in for the unstable [`core::alloc::Allocator`], so custom allocators work on stable Rust.
+    ///
+    /// # Safety
+    ///
+    /// - A block returned by [`allocate`](Self::allocate) must be valid for reads and writes of `layout.size()` bytes,
+    ///   aligned to `layout.align()`, and not be handed out again until it is passed to [`deallocate`](Self::deallocate).
+    /// - Blocks must stay valid while the allocator is moved, and for as long as the allocator (or any clone of it) is alive.
+    pub unsafe trait Allocator {
+        /// Allocate a block of memory that fits `layout`.
+        ///
+        /// Zero-sized layouts may return a dangling (but aligned) pointer.
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+        /// Allocate a block of memory that fits `layout`, with every byte set to zero.
+        fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            let block = self.allocate(layout)?;
+            // SAFETY: `allocate` returned a block that is valid for writes of `layout.size()` bytes
+            unsafe { block.write_bytes(0, layout.size()) };
+            Ok(block)
+        }
+
+        /// Give a block of memory back to the allocator.
+        ///
+        /// # Safety
+        ///
+        /// `ptr` must have been returned by [`allocate`](Self::allocate) (or [`allocate_zeroed`](Self::allocate_zeroed))
+        /// on this allocator (or a clone of it) with the same `layout`, and must not be used again after this call.
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+    }
+
+    /// The global heap, as used by [`alloc::boxed::Box`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct Global;
+
+    impl Global {
+        /// Aligned, non-null pointer for zero-sized layouts, which can't be passed to the global allocator.
+        fn dangling(layout: Layout) -> NonNull<u8> {
+            // SAFETY: alignments are never zero
+            unsafe { NonNull::new_unchecked(core::ptr::without_provenance_mut(layout.align())) }
+        }
+    }
+
+    // SAFETY: blocks come straight from the global allocator (which is `'static`), and zero-sized layouts never touch memory
+    unsafe impl Allocator for Global {
+        #[inline]
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            if layout.size() == 0 {
+                return Ok(Self::dangling(layout));
+            }
+            // SAFETY: `layout` is not zero-size
+            let raw = unsafe { alloc::alloc::alloc(layout) };
+            NonNull::new(raw).ok_or(AllocError::new(()))
+        }
+
+        #[inline]
+        fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            if layout.size() == 0 {
+                return Ok(Self::dangling(layout));
+            }
+            // SAFETY: `layout` is not zero-size
+            let raw = unsafe { alloc::alloc::alloc_zeroed(layout) };
+            NonNull::new(raw).ok_or(AllocError::new(()))
+        }
+
+        #[inline]
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            if layout.size() == 0 {
+                // zero-sized blocks were never really allocated
+                return;
+            }
+            // SAFETY: the caller guarantees `ptr` came from `allocate` with this (non-zero-size) `layout`
+            unsafe { alloc::alloc::dealloc(ptr.as_ptr(), layout) };
+        }
+    }
+
+    // SAFETY: every call is forwarded to the referenced allocator, which upholds the same requirements
+    unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+        #[inline]
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            A::allocate(self, layout)
+        }
+
+        #[inline]
+        fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            A::allocate_zeroed(self, layout)
+        }
+
+        #[inline]
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            // SAFETY: the caller upholds `deallocate`'s requirements
+            unsafe { A::deallocate(self, ptr, layout) }
+        }
+    }
+}
+
+/// The error returned when a memory allocation fails.
+///
+/// Rather than dropping it, the error carries the value that couldn't be placed on the heap (if any),
+/// so the caller can recover it with [`into_inner`](Self::into_inner).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError<T = ()> {
+    value: T,
+}
+
+impl<T> AllocError<T> {
+    /// Create an allocation error, holding on to the `value` that couldn't be allocated.
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Recover the value that couldn't be allocated.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// Rust-specific helper to describe the error to users.
+impl<T> fmt::Display for AllocError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for AllocError<T> {}
+
+/// The error returned when a data structure can't make room for more values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity is larger than `usize::MAX` values, or `isize::MAX` bytes.
+    CapacityOverflow,
+    /// The allocator couldn't provide a block of memory with the given layout.
+    AllocError {
+        /// The layout of the block that couldn't be allocated.
+        layout: Layout,
+    },
+}
+
+/// Rust-specific helper to describe the error to users.
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityOverflow => f.write_str("capacity overflow"),
+            Self::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl core::error::Error for TryReserveError {}
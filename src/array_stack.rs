@@ -0,0 +1,387 @@
+//! Fixed-capacity stack data structure, which never allocates.
+//!
+//! Every value is stored inline, in an array of `N` slots, so the stack can live on the stack (or in a `static`),
+//! and works without a global allocator.
+
+use core::{fmt, iter::FusedIterator};
+
+pub use implementation::{ArrayStack, IntoIter};
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use super::CapacityError;
+    use core::{
+        mem::{ManuallyDrop, MaybeUninit},
+        ptr, slice,
+    };
+
+    /// Last-in, first-out stack with room for at most `N` values, stored inline.
+    ///
+    /// Pushing onto a full stack fails (giving the value back) instead of growing.
+    pub struct ArrayStack<T, const N: usize> {
+        // invariant: exactly the slots in `0..len` are initialized, and `len <= N`
+        values: [MaybeUninit<T>; N],
+        len: usize,
+    }
+
+    impl<T, const N: usize> ArrayStack<T, N> {
+        /// Create an empty stack.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                values: [const { MaybeUninit::uninit() }; N],
+                len: 0,
+            }
+        }
+
+        /// Number of values on the stack.
+        #[doc(alias = "size")]
+        #[inline]
+        pub const fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Maximum number of values the stack can hold, which is always `N`.
+        #[inline]
+        pub const fn capacity(&self) -> usize {
+            N
+        }
+
+        /// View the values as a slice, from the bottom of the stack to the top.
+        #[inline]
+        pub const fn as_slice(&self) -> &[T] {
+            // SAFETY: the slots in `0..len` are initialized
+            unsafe { slice::from_raw_parts(self.values.as_ptr().cast::<T>(), self.len) }
+        }
+
+        /// Mutably view the values as a slice, from the bottom of the stack to the top.
+        #[inline]
+        pub const fn as_mut_slice(&mut self) -> &mut [T] {
+            // SAFETY: the slots in `0..len` are initialized, and the slice mutably borrows the stack
+            unsafe { slice::from_raw_parts_mut(self.values.as_mut_ptr().cast::<T>(), self.len) }
+        }
+
+        /// Add `value` to the top of the stack.
+        ///
+        /// # Errors
+        ///
+        /// If the stack is already full, `value` is given back inside the error, and the stack is unchanged.
+        pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+            let Some(slot) = self.values.get_mut(self.len) else {
+                return Err(CapacityError { value });
+            };
+            slot.write(value);
+            self.len += 1;
+            Ok(())
+        }
+
+        /// Remove the value at the top of the stack and return it, or `None` if it is empty.
+        pub fn pop(&mut self) -> Option<T> {
+            self.len = self.len.checked_sub(1)?;
+            // SAFETY: the slot was initialized, and is now outside of `len` so it is never read again
+            Some(unsafe { self.values[self.len].assume_init_read() })
+        }
+
+        /// Drop every value on the stack.
+        pub fn clear(&mut self) {
+            let values = ptr::from_mut(self.as_mut_slice());
+            // forget the values first, so that a panicking destructor can't lead to a double drop
+            self.len = 0;
+            // SAFETY: the values were initialized, and are now outside of `len` so they are never accessed again
+            unsafe { values.drop_in_place() };
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Only the initialized slots are dropped.
+    impl<T, const N: usize> Drop for ArrayStack<T, N> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    /// Owning iterator over the values of an [`ArrayStack`], created by [`ArrayStack::into_iter`](IntoIterator::into_iter).
+    ///
+    /// Values are yielded from the top of the stack to the bottom, in the same order as popping them
+    /// (or from the bottom to the top, with [`rev`](Iterator::rev)).
+    /// Any values that weren't yielded are dropped when the iterator is dropped.
+    pub struct IntoIter<T, const N: usize> {
+        // invariant: exactly the slots in `start..end` are initialized, and `start <= end <= N`
+        values: [MaybeUninit<T>; N],
+        start: usize,
+        end: usize,
+    }
+
+    impl<T, const N: usize> IntoIter<T, N> {
+        /// View the values that haven't been yielded yet, from the bottom of the stack to the top.
+        #[inline]
+        pub fn as_slice(&self) -> &[T] {
+            // SAFETY: the slots in `start..end` are initialized
+            unsafe {
+                slice::from_raw_parts(
+                    self.values.as_ptr().add(self.start).cast::<T>(),
+                    self.end - self.start,
+                )
+            }
+        }
+    }
+
+    /// Rust-specific helper to move the values out of a stack, from the top to the bottom.
+    impl<T, const N: usize> IntoIterator for ArrayStack<T, N> {
+        type Item = T;
+        type IntoIter = IntoIter<T, N>;
+
+        fn into_iter(self) -> IntoIter<T, N> {
+            // the values now belong to the iterator, so the stack must not drop them
+            let this = ManuallyDrop::new(self);
+            IntoIter {
+                // SAFETY: `this` is never used (or dropped) again, so this is a move out of it
+                values: unsafe { ptr::read(&this.values) },
+                start: 0,
+                end: this.len,
+            }
+        }
+    }
+
+    impl<T, const N: usize> Iterator for IntoIter<T, N> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            self.end -= 1;
+            // SAFETY: the slot at `end` was initialized, and is never read again once `end` moves before it
+            Some(unsafe { self.values[self.end].assume_init_read() })
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.end - self.start;
+            (len, Some(len))
+        }
+    }
+
+    impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+        fn next_back(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            // SAFETY: the slot at `start` is initialized, and is never read again once `start` moves past it
+            let value = unsafe { self.values[self.start].assume_init_read() };
+            self.start += 1;
+            Some(value)
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Only the values that weren't yielded are dropped.
+    impl<T, const N: usize> Drop for IntoIter<T, N> {
+        fn drop(&mut self) {
+            // SAFETY: `start <= N`, so the pointer stays within the array
+            let rest = ptr::slice_from_raw_parts_mut(
+                unsafe { self.values.as_mut_ptr().add(self.start) }.cast::<T>(),
+                self.end - self.start,
+            );
+            // forget the values first, so that a panicking destructor can't lead to a double drop
+            self.start = self.end;
+            // SAFETY: the values were initialized, and are now outside of `start..end` so they are never accessed again
+            unsafe { rest.drop_in_place() };
+        }
+    }
+}
+
+/// Error returned when pushing onto a full [`ArrayStack`], giving back the value that didn't fit.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    value: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Take back the value that couldn't be pushed.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// Rust-specific helper to visualize the error, without requiring the value to be printable.
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapacityError").finish_non_exhaustive()
+    }
+}
+
+/// Rust-specific helper to describe the error to users.
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T> core::error::Error for CapacityError<T> {}
+
+impl<T, const N: usize> ArrayStack<T, N> {
+    /// Check whether the stack has no values.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check whether the stack has no room for another value.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Get a reference to the value at the top of the stack, or `None` if it is empty.
+    #[doc(alias = "peek")]
+    #[inline]
+    pub fn top(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    /// Get a mutable reference to the value at the top of the stack, or `None` if it is empty.
+    #[inline]
+    pub fn top_mut(&mut self) -> Option<&mut T> {
+        self.as_mut_slice().last_mut()
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T, const N: usize> Default for ArrayStack<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way, from the bottom of the stack to the top.
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayStack<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator, from the bottom of the stack to the top.
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_drop::CountDrops;
+
+    #[test]
+    fn push_pop_top() {
+        use crate::test_alloc::count_allocations;
+
+        let (stack, allocations) = count_allocations(|| {
+            let mut stack = ArrayStack::<i32, 4>::new();
+            assert_eq!(stack.top(), None);
+            assert_eq!(stack.pop(), None);
+            for i in 1..=3 {
+                stack.push(i).unwrap();
+            }
+            assert_eq!(stack.top(), Some(&3));
+            *stack.top_mut().unwrap() = 30;
+            assert_eq!(stack.pop(), Some(30));
+            stack.push(4).unwrap();
+            stack
+        });
+        assert_eq!(allocations, 0);
+        assert_eq!((stack.len(), stack.capacity()), (3, 4));
+        assert_eq!(stack.as_slice(), [1, 2, 4]);
+        assert_eq!(std::format!("{stack:?}"), "[1, 2, 4]");
+    }
+
+    #[test]
+    fn full_push_gives_value_back() {
+        let mut stack = ArrayStack::<std::string::String, 2>::new();
+        stack.push("a".into()).unwrap();
+        assert!(!stack.is_full());
+        stack.push("b".into()).unwrap();
+        assert!(stack.is_full());
+
+        let error = stack.push("c".into()).unwrap_err();
+        assert_eq!(
+            std::format!("{error} {error:?}"),
+            "insufficient capacity CapacityError { .. }"
+        );
+        assert_eq!(error.into_inner(), "c");
+        assert_eq!(stack.as_slice(), ["a", "b"]);
+        assert_eq!(stack.pop().as_deref(), Some("b"));
+        assert!(stack.push("d".into()).is_ok());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut stack = ArrayStack::<i32, 0>::default();
+        assert!(stack.is_empty() && stack.is_full());
+        assert_eq!(stack.push(1).map_err(CapacityError::into_inner), Err(1));
+        assert_eq!((stack.pop(), stack.top()), (None, None));
+        assert_eq!(stack.into_iter().next(), None);
+    }
+
+    #[test]
+    fn drops_only_initialized_slots() {
+        let drops = core::cell::Cell::new(0);
+        let mut stack = ArrayStack::<_, 8>::new();
+        for i in 0..5 {
+            stack.push(CountDrops::new(i, &drops)).unwrap();
+        }
+        drop(stack.pop());
+        assert_eq!(drops.get(), 1);
+        drop(stack);
+        assert_eq!(drops.get(), 5);
+
+        let mut stack = ArrayStack::<_, 3>::new();
+        for i in 0..2 {
+            stack.push(CountDrops::new(i, &drops)).unwrap();
+        }
+        stack.clear();
+        assert_eq!(drops.get(), 7);
+        assert!(stack.is_empty());
+        drop(stack);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    fn into_iter_pops_from_top() {
+        let drops = core::cell::Cell::new(0);
+        let mut stack = ArrayStack::<_, 6>::new();
+        for i in 0..5 {
+            stack.push(CountDrops::new(i, &drops)).unwrap();
+        }
+        let mut iter = stack.into_iter();
+        assert_eq!(iter.next().map(|value| value.value), Some(4));
+        assert_eq!(iter.next_back().map(|value| value.value), Some(0));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(
+            iter.as_slice().iter().map(|value| value.value).sum::<i32>(),
+            6
+        );
+        assert_eq!(drops.get(), 2);
+        drop(iter);
+        assert_eq!(drops.get(), 5);
+
+        let mut stack = ArrayStack::<_, 3>::new();
+        for i in 1..=3 {
+            stack.push(i).unwrap();
+        }
+        let mut iter = stack.into_iter();
+        iter.next();
+        assert_eq!(std::format!("{iter:?}"), "IntoIter([1, 2])");
+        assert!(iter.eq([2, 1]));
+    }
+}
@@ -0,0 +1,770 @@
+//! Binary heap (priority queue) data structure.
+//!
+//! The values are stored in a flat [`MyVec`], as a complete binary tree in level order: the children of the value at
+//! index `i` are at `2 * i + 1` and `2 * i + 2`. Every value is at least as great as its children, so the greatest
+//! value is always at the root, and pushing or popping only has to fix up one path from the root to a leaf.
+//!
+//! "Greatest" can be according to any order, such as a reversed order for a min-heap, or an order by some key.
+
+use crate::my_vec::{self, MyVec};
+use core::{cmp::Ordering, fmt, iter::FusedIterator};
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use core::{mem::ManuallyDrop, ptr};
+
+    /// A slice with one value temporarily moved out, leaving a "hole" that can be moved around.
+    ///
+    /// Sifting moves other values into the hole instead of swapping, so each step is one copy rather than three.
+    /// The value is written back into wherever the hole ends up when this is dropped, even if a comparison panics,
+    /// so no value is ever lost or duplicated.
+    struct Hole<'a, T> {
+        // invariant: `pos < data.len()`, and the slot at `pos` is a bitwise duplicate of some other value (or of `elt`)
+        data: &'a mut [T],
+        elt: ManuallyDrop<T>,
+        pos: usize,
+    }
+
+    impl<'a, T> Hole<'a, T> {
+        /// Move the value at `pos` out of `data`, leaving a hole.
+        ///
+        /// # Panics
+        ///
+        /// If `pos` is out of bounds.
+        fn new(data: &'a mut [T], pos: usize) -> Self {
+            // SAFETY: `pos` is in bounds (checked by indexing), and the value is written back when the hole is dropped
+            let elt = unsafe { ptr::read(&data[pos]) };
+            Self {
+                data,
+                elt: ManuallyDrop::new(elt),
+                pos,
+            }
+        }
+
+        /// The value that was moved out.
+        #[inline]
+        fn element(&self) -> &T {
+            &self.elt
+        }
+
+        /// Get the value at `index`, which must not be the hole.
+        ///
+        /// # Panics
+        ///
+        /// If `index` is out of bounds, or is the hole itself.
+        #[inline]
+        fn get(&self, index: usize) -> &T {
+            assert!(index != self.pos, "can't read from the hole");
+            &self.data[index]
+        }
+
+        /// Move the value at `index` into the hole, which moves the hole to `index`.
+        ///
+        /// # Panics
+        ///
+        /// If `index` is out of bounds, or is the hole itself.
+        #[inline]
+        fn move_to(&mut self, index: usize) {
+            assert!(
+                index != self.pos && index < self.data.len(),
+                "can't move the hole out of bounds, or onto itself"
+            );
+            let ptr = self.data.as_mut_ptr();
+            // SAFETY: both indices are in bounds and distinct, and the value at `index` becomes the duplicate instead
+            unsafe { ptr::copy_nonoverlapping(ptr.add(index), ptr.add(self.pos), 1) };
+            self.pos = index;
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Fills the hole with the value that was moved out.
+    impl<T> Drop for Hole<'_, T> {
+        fn drop(&mut self) {
+            // SAFETY: `pos` is in bounds, and only holds a duplicate, which is overwritten without being dropped
+            unsafe {
+                ptr::copy_nonoverlapping(&raw const *self.elt, &raw mut self.data[self.pos], 1)
+            };
+        }
+    }
+
+    /// Move the value at `pos` up towards the root of a heap where every value has `D` children, for as long as it is
+    /// greater than its parent, and return its new position.
+    ///
+    /// `is_less(a, b)` decides whether `a` belongs below `b`.
+    ///
+    /// # Panics
+    ///
+    /// If `pos` is out of bounds, or `D` is 0. If `is_less` panics, every value is still in `data` exactly once.
+    pub(super) fn sift_up<const D: usize, T>(
+        data: &mut [T],
+        pos: usize,
+        is_less: &mut impl FnMut(&T, &T) -> bool,
+    ) -> usize {
+        let mut hole = Hole::new(data, pos);
+        while hole.pos > 0 {
+            let parent = (hole.pos - 1) / D;
+            if !is_less(hole.get(parent), hole.element()) {
+                break;
+            }
+            hole.move_to(parent);
+        }
+        hole.pos
+    }
+
+    /// Move the value at `pos` down towards the leaves of a heap where every value has `D` children (only looking at
+    /// the first `end` values), for as long as it is less than its greatest child.
+    ///
+    /// `is_less(a, b)` decides whether `a` belongs below `b`.
+    ///
+    /// # Panics
+    ///
+    /// If `pos` or `end` is out of bounds. If `is_less` panics, every value is still in `data` exactly once.
+    pub(super) fn sift_down_range<const D: usize, T>(
+        data: &mut [T],
+        pos: usize,
+        end: usize,
+        is_less: &mut impl FnMut(&T, &T) -> bool,
+    ) {
+        assert!(end <= data.len(), "sifting past the end of the heap");
+        let mut hole = Hole::new(data, pos);
+        // saturating, since a huge `D` could overflow (but then there are no children anyway)
+        let mut first_child = D.saturating_mul(hole.pos).saturating_add(1);
+        while first_child < end {
+            // pick the greatest child (the leftmost one, if they are tied)
+            let mut child = first_child;
+            for sibling in first_child + 1..first_child.saturating_add(D).min(end) {
+                if is_less(hole.get(child), hole.get(sibling)) {
+                    child = sibling;
+                }
+            }
+            if !is_less(hole.element(), hole.get(child)) {
+                return;
+            }
+            hole.move_to(child);
+            first_child = D.saturating_mul(hole.pos).saturating_add(1);
+        }
+    }
+}
+
+/// Priority queue of values, where the greatest value according to an arbitrary order `F` is always the next to be
+/// popped.
+///
+/// The values form a tree where each one has up to `D` children: two by default, for a binary heap, or more for a
+/// shallower [d-ary heap](crate::d_ary_heap). Pushing is O(log n), popping is O(D log n) (both with logarithms in base
+/// `D`), and peeking at the greatest value is O(1). Values that are tied come out in an unspecified order.
+///
+/// ```
+/// # use datastructures_and_algorithms::binary_heap::MyBinaryHeapBy;
+/// let mut min_heap = MyBinaryHeapBy::with_order(|a: &i32, b: &i32| b.cmp(a));
+/// min_heap.push(3);
+/// min_heap.push(1);
+/// min_heap.push(2);
+/// assert_eq!(min_heap.pop(), Some(1));
+/// ```
+pub struct MyBinaryHeapBy<T, F, const D: usize = 2> {
+    // invariant: no value in `data` is greater (by `order`) than its parent, at index `(i - 1) / D`
+    data: MyVec<T>,
+    order: F,
+}
+
+/// Priority queue of values, where the greatest value is always the next to be popped, as a [`MyBinaryHeapBy`]
+/// ordered by [`Ord`].
+///
+/// For the least value instead, push values wrapped in [`core::cmp::Reverse`], or use [`MyBinaryHeapBy::with_order`]
+/// or [`MyBinaryHeap::by_key`].
+#[doc(alias = "MyPriorityQueue")]
+pub type MyBinaryHeap<T> = MyBinaryHeapBy<T, fn(&T, &T) -> Ordering>;
+
+impl<T, F: Fn(&T, &T) -> Ordering> MyBinaryHeapBy<T, F> {
+    /// Create an empty heap, which puts the greatest value according to `order` on top, without allocating.
+    #[inline]
+    pub const fn with_order(order: F) -> Self {
+        Self {
+            data: MyVec::new(),
+            order,
+        }
+    }
+
+    /// Create an empty heap, which puts the greatest value according to `order` on top, with room for at least
+    /// `capacity` values before it needs to reallocate.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer would be larger than `isize::MAX` bytes.
+    #[inline]
+    pub fn with_capacity_and_order(capacity: usize, order: F) -> Self {
+        Self {
+            data: MyVec::with_capacity(capacity),
+            order,
+        }
+    }
+}
+
+impl<T, F, const D: usize> MyBinaryHeapBy<T, F, D> {
+    /// Evaluated by every constructor that takes `D` as a parameter, so that a heap with fewer than two children per
+    /// value fails to compile.
+    const VALID_ARITY: () = assert!(D >= 2, "a heap needs at least 2 children per value");
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering, const D: usize> MyBinaryHeapBy<T, F, D> {
+    /// Add `value` to the heap, in O(log n).
+    ///
+    /// If comparing values panics, `value` is still added (but the order of the heap is unspecified).
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+        let last = self.data.len() - 1;
+        implementation::sift_up::<D, _>(&mut self.data, last, &mut |a, b| {
+            (self.order)(a, b).is_lt()
+        });
+    }
+
+    /// Remove the greatest value from the heap and return it, or `None` if it is empty, in O(D log n).
+    ///
+    /// If comparing values panics, the popped value is dropped, and the rest stay in the heap (in an unspecified order).
+    pub fn pop(&mut self) -> Option<T> {
+        let mut value = self.data.pop()?;
+        if !self.data.is_empty() {
+            // the old last value takes the root's place, then sinks back down to where it belongs
+            core::mem::swap(&mut value, &mut self.data[0]);
+            let len = self.data.len();
+            implementation::sift_down_range::<D, _>(&mut self.data, 0, len, &mut |a, b| {
+                (self.order)(a, b).is_lt()
+            });
+        }
+        Some(value)
+    }
+
+    /// Move every value from `other` into this heap, leaving `other` empty.
+    ///
+    /// The values are ordered by this heap's order afterwards, even if `other` had a different one. If `other` is the
+    /// smaller heap, its values are pushed one by one, in O(m log(n + m)) for its size `m`; otherwise, the whole heap
+    /// is rebuilt in O(n + m).
+    pub fn append(&mut self, other: &mut Self) {
+        if self.len() < other.len() {
+            self.data.append(&mut other.data);
+            self.rebuild();
+        } else {
+            self.data.reserve(other.len());
+            for value in other.data.drain(..) {
+                self.push(value);
+            }
+        }
+    }
+
+    /// Consume the heap and return its values sorted from least to greatest, in O(n D log n), without allocating.
+    ///
+    /// If comparing values panics, every value is dropped (in an unspecified order).
+    pub fn into_sorted_vec(mut self) -> MyVec<T> {
+        // repeatedly move the greatest remaining value to the end of the shrinking heap, just before the sorted part
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            implementation::sift_down_range::<D, _>(&mut self.data, 0, end, &mut |a, b| {
+                (self.order)(a, b).is_lt()
+            });
+        }
+        self.data
+    }
+
+    /// Remove the values from greatest to least, lazily: each one is only popped (in O(D log n)) when the iterator
+    /// reaches it.
+    ///
+    /// If the iterator is dropped early, the values it didn't reach stay behind in the heap, which is still valid.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, F, D> {
+        DrainSorted { heap: self }
+    }
+
+    /// Check whether no value is greater than its parent, in O(n).
+    ///
+    /// This always holds for a heap that was built and modified through its methods (unless a comparison panicked,
+    /// or the order of values changed while they were in the heap), so it is mostly useful for debugging.
+    pub fn is_heap(&self) -> bool {
+        (1..self.data.len()).all(|i| (self.order)(&self.data[i], &self.data[(i - 1) / D]).is_le())
+    }
+
+    /// Rearrange the values into a valid heap, in O(n), by sifting down every value with children, bottom-up.
+    fn rebuild(&mut self) {
+        let len = self.data.len();
+        // the value at `pos` has children if `D * pos + 1 < len`
+        for pos in (0..len.saturating_sub(1).div_ceil(D)).rev() {
+            implementation::sift_down_range::<D, _>(&mut self.data, pos, len, &mut |a, b| {
+                (self.order)(a, b).is_lt()
+            });
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> MyBinaryHeapBy<T, fn(&T, &T) -> Ordering, D> {
+    /// Create an empty heap, without allocating.
+    #[inline]
+    pub const fn new() -> Self {
+        let () = Self::VALID_ARITY;
+        Self {
+            data: MyVec::new(),
+            order: T::cmp,
+        }
+    }
+
+    /// Create an empty heap with room for at least `capacity` values before it needs to reallocate.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer would be larger than `isize::MAX` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let () = Self::VALID_ARITY;
+        Self {
+            data: MyVec::with_capacity(capacity),
+            order: T::cmp,
+        }
+    }
+}
+
+impl<T> MyBinaryHeap<T> {
+    /// Create an empty heap, which puts the value with the greatest key on top, without allocating.
+    ///
+    /// `key` is called twice per comparison, so O(log n) times per push or pop. For the least key on top instead,
+    /// return the key wrapped in [`core::cmp::Reverse`].
+    ///
+    /// ```
+    /// # use datastructures_and_algorithms::binary_heap::MyBinaryHeap;
+    /// let mut shortest_first = MyBinaryHeap::by_key(|word: &&str| core::cmp::Reverse(word.len()));
+    /// shortest_first.push("forest");
+    /// shortest_first.push("a");
+    /// shortest_first.push("tree");
+    /// assert_eq!(shortest_first.pop(), Some("a"));
+    /// ```
+    #[inline]
+    pub fn by_key<K: Ord>(key: impl Fn(&T) -> K) -> MyBinaryHeapBy<T, impl Fn(&T, &T) -> Ordering> {
+        MyBinaryHeapBy::with_order(move |a: &T, b: &T| key(a).cmp(&key(b)))
+    }
+}
+
+impl<T, F, const D: usize> MyBinaryHeapBy<T, F, D> {
+    /// Number of values in the heap.
+    #[doc(alias = "size")]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Check whether the heap has no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Number of values the heap can hold before it needs to reallocate.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Get a reference to the greatest value, or `None` if the heap is empty, in O(1).
+    #[doc(alias = "top")]
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// View the values in their internal (level) order, which is only partially sorted.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Drop every value in the heap.
+    ///
+    /// The capacity is unchanged.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Consume the heap and return its values in their internal (level) order, in O(1).
+    #[inline]
+    pub fn into_vec(self) -> MyVec<T> {
+        self.data
+    }
+
+    /// Consume the heap and move its values out in their internal (level) order, which is only partially sorted.
+    ///
+    /// This is O(1) to start, and O(1) per value, unlike popping them in order.
+    #[inline]
+    pub fn into_iter_unordered(self) -> my_vec::IntoIter<T> {
+        self.data.into_iter()
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T: Ord, const D: usize> Default for MyBinaryHeapBy<T, fn(&T, &T) -> Ordering, D> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+impl<T: Clone, F: Clone, const D: usize> Clone for MyBinaryHeapBy<T, F, D> {
+    fn clone(&self) -> Self {
+        // the same values in the same order are still a valid heap
+        Self {
+            data: self.data.clone(),
+            order: self.order.clone(),
+        }
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way, in their internal (level) order.
+impl<T: fmt::Debug, F, const D: usize> fmt::Debug for MyBinaryHeapBy<T, F, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+/// Rust-specific helper to turn a vector into a heap of the same values, in O(n).
+impl<T: Ord, const D: usize> From<MyVec<T>> for MyBinaryHeapBy<T, fn(&T, &T) -> Ordering, D> {
+    fn from(data: MyVec<T>) -> Self {
+        let () = Self::VALID_ARITY;
+        let mut heap = Self {
+            data,
+            order: T::cmp,
+        };
+        heap.rebuild();
+        heap
+    }
+}
+
+/// Rust-specific helper to allow collecting an iterator into a heap, in O(n) once the values are collected.
+impl<T: Ord, const D: usize> FromIterator<T> for MyBinaryHeapBy<T, fn(&T, &T) -> Ordering, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<MyVec<_>>())
+    }
+}
+
+/// Iterator that pops values from a [`MyBinaryHeapBy`] from greatest to least, created by
+/// [`MyBinaryHeapBy::drain_sorted`].
+pub struct DrainSorted<'a, T, F: Fn(&T, &T) -> Ordering, const D: usize = 2> {
+    heap: &'a mut MyBinaryHeapBy<T, F, D>,
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering, const D: usize> Iterator for DrainSorted<'_, T, F, D> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering, const D: usize> ExactSizeIterator for DrainSorted<'_, T, F, D> {}
+
+impl<T, F: Fn(&T, &T) -> Ordering, const D: usize> FusedIterator for DrainSorted<'_, T, F, D> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator, in their internal (level) order.
+impl<T: fmt::Debug, F: Fn(&T, &T) -> Ordering, const D: usize> fmt::Debug
+    for DrainSorted<'_, T, F, D>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DrainSorted").field(&self.heap).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_drop::CountDrops, test_rng::TestRng};
+
+    /// The values `0..len` in a scrambled (but reproducible) order.
+    fn shuffled(len: u64) -> impl Iterator<Item = u64> {
+        // multiplying by a number coprime to `len` visits every residue exactly once
+        (0..len).map(move |i| (i * 7_919 + 13) % len)
+    }
+
+    #[test]
+    fn pops_in_descending_order() {
+        let mut heap = MyBinaryHeap::new();
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+        for value in shuffled(1_000) {
+            heap.push(value);
+        }
+        assert_eq!((heap.len(), heap.peek()), (1_000, Some(&999)));
+        let popped: std::vec::Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert!(popped.into_iter().eq((0..1_000).rev()));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let mut heap = MyBinaryHeap::with_capacity(10);
+        for value in [3, 1, 3, 2, 1, 3, 2] {
+            heap.push(value);
+        }
+        assert_eq!(heap.capacity(), 10);
+        let mut popped = std::vec::Vec::new();
+        while let Some(value) = heap.pop() {
+            popped.push(value);
+        }
+        assert_eq!(popped, [3, 3, 3, 2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn append_clone_and_debug() {
+        let mut small = MyBinaryHeap::new();
+        let mut large = MyBinaryHeap::new();
+        small.push(10);
+        for value in [1, 5, 3] {
+            large.push(value);
+        }
+        small.append(&mut large);
+        assert_eq!((small.len(), large.len()), (4, 0));
+        assert_eq!(small.peek(), Some(&10));
+        assert_eq!(
+            std::format!("{small:?}"),
+            std::format!("{:?}", small.as_slice())
+        );
+
+        let mut clone = small.clone();
+        assert_eq!(clone.pop(), Some(10));
+        assert_eq!(clone.pop(), Some(5));
+        assert_eq!(small.len(), 4);
+        small.clear();
+        assert!(small.is_empty());
+    }
+
+    #[test]
+    fn drops_each_value_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut heap = MyBinaryHeap::new();
+        for value in shuffled(20) {
+            heap.push(CountDrops::new(value, &drops));
+        }
+        assert_eq!(heap.pop().map(|value| value.value), Some(19));
+        assert_eq!(drops.get(), 1);
+        drop(heap);
+        assert_eq!(drops.get(), 20);
+    }
+
+    #[test]
+    fn heapify_is_valid() {
+        for len in [0, 1, 2, 3, 10, 100, 1_000] {
+            let heap = MyBinaryHeap::from(shuffled(len).collect::<MyVec<_>>());
+            assert!(heap.is_heap());
+            assert_eq!(
+                (heap.len(), heap.peek()),
+                (len as usize, len.checked_sub(1).as_ref())
+            );
+
+            let collected: MyBinaryHeap<_> = shuffled(len).map(|value| value % 7).collect();
+            assert!(collected.is_heap());
+        }
+        // a vector that is already in ascending order is the worst case for a max-heap
+        assert!(MyBinaryHeap::from((0..100).collect::<MyVec<_>>()).is_heap());
+
+        let mut not_a_heap = MyBinaryHeap::new();
+        not_a_heap.data.extend([1, 2]);
+        assert!(!not_a_heap.is_heap());
+    }
+
+    #[test]
+    fn into_sorted_vec_matches_sort() {
+        let values: std::vec::Vec<_> = TestRng::values(7, 500, 100).collect();
+        let heap: MyBinaryHeap<_> = values.iter().copied().collect();
+        let mut expected = values.clone();
+        expected.sort_unstable();
+        assert_eq!(*heap.clone().into_sorted_vec(), expected[..]);
+
+        // the unordered views hold the same values, in the same (level) order
+        let level_order = heap.as_slice().to_vec();
+        assert_eq!(
+            heap.clone()
+                .into_iter_unordered()
+                .collect::<std::vec::Vec<_>>(),
+            level_order
+        );
+        assert_eq!(*heap.into_vec(), level_order[..]);
+        assert!(MyBinaryHeap::<u64>::new().into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn drop_drain_sorted_halfway() {
+        let mut heap: MyBinaryHeap<_> = shuffled(100).collect();
+        {
+            let mut drain = heap.drain_sorted();
+            assert_eq!(drain.len(), 100);
+            assert!(drain.by_ref().take(50).eq((50..100).rev()));
+            assert_eq!(drain.len(), 50);
+        }
+
+        assert!(heap.is_heap());
+        assert_eq!((heap.len(), heap.peek()), (50, Some(&49)));
+        assert!(heap.drain_sorted().eq((0..50).rev()));
+        assert!(heap.is_empty());
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Task {
+        priority: u32,
+        name: &'static str,
+    }
+
+    #[test]
+    fn min_heap_by_field() {
+        let tasks = [
+            (3, "write"),
+            (1, "plan"),
+            (4, "test"),
+            (2, "design"),
+            (5, "ship"),
+        ]
+        .map(|(priority, name)| Task { priority, name });
+
+        let mut by_key = MyBinaryHeap::by_key(|task: &Task| core::cmp::Reverse(task.priority));
+        // the order may also borrow from its surroundings
+        let weights = [0, 50, 40, 30, 20, 10];
+        let mut by_weight = MyBinaryHeapBy::with_capacity_and_order(5, |a: &Task, b: &Task| {
+            weights[b.priority as usize].cmp(&weights[a.priority as usize])
+        });
+        for task in &tasks {
+            by_key.push(task.clone());
+            by_weight.push(task.clone());
+        }
+        assert!(by_key.is_heap() && by_weight.is_heap());
+        assert_eq!(by_key.peek().map(|task| task.name), Some("plan"));
+
+        let names: std::vec::Vec<_> = by_key.drain_sorted().map(|task| task.name).collect();
+        assert_eq!(names, ["plan", "design", "write", "test", "ship"]);
+        let names: std::vec::Vec<_> = by_weight
+            .into_sorted_vec()
+            .iter()
+            .map(|task| task.name)
+            .collect();
+        assert_eq!(names, ["plan", "design", "write", "test", "ship"]);
+    }
+
+    #[test]
+    fn ties_come_out_together() {
+        let mut heap = MyBinaryHeap::by_key(|&(key, _): &(u64, u64)| core::cmp::Reverse(key));
+        for (i, key) in shuffled(60).map(|value| value % 6).enumerate() {
+            heap.push((key, i as u64));
+        }
+        let mut seen = std::vec::Vec::new();
+        while let Some((key, i)) = heap.pop() {
+            seen.push(i);
+            assert!(heap.peek().is_none_or(|&(next, _)| next >= key));
+        }
+        seen.sort_unstable();
+        assert!(seen.into_iter().eq(0..60));
+    }
+
+    #[test]
+    fn key_calls_are_logarithmic() {
+        let calls = core::cell::Cell::new(0);
+        let mut heap = MyBinaryHeap::by_key(|&value: &u64| {
+            calls.set(calls.get() + 1);
+            value
+        });
+        // every level costs one comparison when pushing and two when popping, each calling `key` twice
+        let bound = |len: usize| 4 * (len.ilog2() as usize + 1);
+        for value in shuffled(1_000) {
+            calls.set(0);
+            heap.push(value);
+            assert!(calls.get() <= bound(heap.len()));
+        }
+        while !heap.is_empty() {
+            let len = heap.len();
+            calls.set(0);
+            heap.pop();
+            assert!(calls.get() <= bound(len));
+        }
+    }
+
+    #[test]
+    fn append_differently_ordered_heap() {
+        let mut max_heap = MyBinaryHeap::new();
+        max_heap.push(2);
+        let mut min_heap = MyBinaryHeap::with_order(|a: &i32, b: &i32| b.cmp(a));
+        for value in [5, 0, 3, 4, 1] {
+            min_heap.push(value);
+        }
+        let mut small_min_heap = min_heap.clone();
+        small_min_heap.clear();
+        small_min_heap.push(7);
+
+        // the larger heap's values move in, and take this heap's order
+        max_heap.append(&mut min_heap);
+        assert!(max_heap.is_heap() && min_heap.is_empty());
+        // the smaller heap's values are pushed
+        max_heap.append(&mut small_min_heap);
+        assert!(max_heap.is_heap() && small_min_heap.is_empty());
+        assert!(max_heap.drain_sorted().eq([7, 5, 4, 3, 2, 1, 0]));
+    }
+
+    /// A value whose comparisons panic once `armed` is set.
+    #[derive(Debug)]
+    struct Panicky<'a> {
+        value: u64,
+        armed: &'a core::cell::Cell<bool>,
+    }
+
+    impl PartialEq for Panicky<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other).is_eq()
+        }
+    }
+
+    impl Eq for Panicky<'_> {}
+
+    impl PartialOrd for Panicky<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Panicky<'_> {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            assert!(!self.armed.get(), "comparison panicked");
+            self.value.cmp(&other.value)
+        }
+    }
+
+    #[test]
+    fn panicking_comparison_keeps_every_value() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let armed = core::cell::Cell::new(false);
+        let mut heap = MyBinaryHeap::new();
+        for value in shuffled(50) {
+            heap.push(Panicky {
+                value,
+                armed: &armed,
+            });
+        }
+        armed.set(true);
+        let push = catch_unwind(AssertUnwindSafe(|| {
+            heap.push(Panicky {
+                value: 100,
+                armed: &armed,
+            });
+        }));
+        assert!(push.is_err());
+        let pop = catch_unwind(AssertUnwindSafe(|| heap.pop()));
+        assert!(pop.is_err());
+        armed.set(false);
+
+        // the pushed value stayed (where it was pushed), and the popped one (the old root) was dropped while
+        // unwinding; the rest are all still there, exactly once
+        assert_eq!(heap.len(), 50);
+        let mut values: std::vec::Vec<_> =
+            heap.as_slice().iter().map(|value| value.value).collect();
+        values.sort_unstable();
+        assert!(values.into_iter().eq((0..49).chain([100])));
+    }
+}
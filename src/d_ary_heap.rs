@@ -0,0 +1,118 @@
+//! D-ary heap (priority queue) data structure.
+//!
+//! This is the same as a [binary heap](crate::binary_heap), but every value has up to `D` children instead of two:
+//! the children of the value at index `i` are at `D * i + 1` through `D * i + D`. A wider tree is shallower, so
+//! pushing only climbs log_D(n) levels, but popping has to compare all `D` children on each of those levels.
+//! The children are next to each other in memory, so for small `D` they are often in the same cache line.
+
+use crate::binary_heap::MyBinaryHeapBy;
+use core::cmp::Ordering;
+
+/// Priority queue of values, where the greatest value is always the next to be popped, stored as a tree where each
+/// value has up to `D` children, as a [`MyBinaryHeapBy`] ordered by [`Ord`].
+///
+/// It has the same methods as a [`MyBinaryHeap`](crate::binary_heap::MyBinaryHeap), which is the same as a
+/// `MyDAryHeap<T, 2>`. `D` must be at least 2, which is checked at compile time:
+///
+/// ```compile_fail
+/// # use datastructures_and_algorithms::d_ary_heap::MyDAryHeap;
+/// let heap = MyDAryHeap::<i32, 1>::new();
+/// ```
+///
+/// ```compile_fail
+/// # use datastructures_and_algorithms::d_ary_heap::MyDAryHeap;
+/// let heap = MyDAryHeap::<i32, 0>::new();
+/// ```
+pub type MyDAryHeap<T, const D: usize> = MyBinaryHeapBy<T, fn(&T, &T) -> Ordering, D>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        binary_heap::MyBinaryHeap, my_vec::MyVec, test_drop::CountDrops, test_rng::TestRng,
+    };
+
+    /// Push and pop the same values (interleaved) in a d-ary and a binary heap, and check they pop the same order.
+    fn matches_binary_heap<const D: usize>() {
+        for seed in 0..if cfg!(miri) { 2 } else { 20 } {
+            let mut heap = MyDAryHeap::<_, D>::new();
+            let mut binary = MyBinaryHeap::new();
+            for (i, value) in TestRng::values(seed, 200, 50).enumerate() {
+                heap.push(value);
+                binary.push(value);
+                if i % 3 == 0 {
+                    assert_eq!(heap.pop(), binary.pop());
+                }
+                assert_eq!(heap.peek(), binary.peek());
+            }
+            assert!(heap.is_heap());
+            assert_eq!(heap.len(), binary.len());
+            while !binary.is_empty() {
+                assert_eq!(heap.pop(), binary.pop());
+            }
+            assert_eq!(heap.pop(), None);
+
+            let heapified =
+                MyDAryHeap::<_, D>::from(TestRng::values(seed, 100, 50).collect::<MyVec<_>>());
+            assert!(heapified.is_heap());
+            let binary: MyBinaryHeap<_> = TestRng::values(seed, 100, 50).collect();
+            assert_eq!(*heapified.into_sorted_vec(), *binary.into_sorted_vec());
+        }
+    }
+
+    #[test]
+    fn same_order_as_binary_heap() {
+        matches_binary_heap::<2>();
+        matches_binary_heap::<3>();
+        matches_binary_heap::<4>();
+        matches_binary_heap::<8>();
+    }
+
+    #[test]
+    fn many_values() {
+        let len = if cfg!(miri) { 1_000 } else { 100_000 };
+        let mut heap: MyDAryHeap<_, 4> = TestRng::values(1, len, usize::MAX).collect();
+        assert!(heap.is_heap());
+        for value in TestRng::values(2, len, usize::MAX) {
+            heap.push(value);
+        }
+        assert_eq!(heap.len(), 2 * len);
+        let mut previous = usize::MAX;
+        while let Some(value) = heap.pop() {
+            assert!(value <= previous);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn small_heaps_and_wide_trees() {
+        let mut heap = MyDAryHeap::<_, 1_000>::with_capacity(3);
+        assert_eq!((heap.pop(), heap.capacity()), (None, 3));
+        heap.push(1);
+        heap.push(3);
+        heap.push(2);
+        // every value is a child of the root
+        assert_eq!(std::format!("{heap:?}"), "[3, 1, 2]");
+        let clone = heap.clone();
+        assert_eq!(*heap.into_vec(), [3, 1, 2]);
+        assert_eq!(clone.as_slice(), [3, 1, 2]);
+
+        let mut heap = MyDAryHeap::<u8, 3>::default();
+        assert!(heap.is_empty() && heap.is_heap());
+        heap.push(7);
+        heap.clear();
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn drops_each_value_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut heap: MyDAryHeap<_, 3> = (0..20)
+            .map(|value| CountDrops::new(value, &drops))
+            .collect();
+        assert_eq!(heap.pop().map(|value| value.value), Some(19));
+        assert_eq!(drops.get(), 1);
+        drop(heap);
+        assert_eq!(drops.get(), 20);
+    }
+}
@@ -0,0 +1,12 @@
+//! Formatting helper shared by the iterators of the containers.
+
+use core::fmt;
+
+/// Formats the values an iterator would yield as a list, without consuming it (by formatting a clone instead).
+pub(crate) struct DebugValues<I>(pub(crate) I);
+
+impl<I: Iterator<Item: fmt::Debug> + Clone> fmt::Debug for DebugValues<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
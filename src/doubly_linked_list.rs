@@ -0,0 +1,1230 @@
+//! Doubly linked list data structure.
+//!
+//! This data structure was originally called "MyLinkedList" in the course.
+//! Every value lives in its own heap node, linked to both its neighbors, so values can be pushed and popped at either end in O(1).
+
+use crate::debug_values::DebugValues;
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+};
+
+pub use implementation::{CursorMut, Iter, IterMut, MyDoublyLinkedList};
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use crate::my_box::MyBox;
+    use core::{marker::PhantomData, mem, ptr::NonNull};
+
+    /// Non-owning link to a neighboring node, if any.
+    type Link<T> = Option<NonNull<Node<T>>>;
+
+    /// A single heap node in the list, allocated by a [`MyBox`].
+    struct Node<T> {
+        value: T,
+        prev: Link<T>,
+        next: Link<T>,
+    }
+
+    impl<T> Node<T> {
+        /// Allocate a new, unlinked node.
+        fn new(value: T) -> NonNull<Self> {
+            NonNull::from(
+                MyBox::new(Self {
+                    value,
+                    prev: None,
+                    next: None,
+                })
+                .leak(),
+            )
+        }
+
+        /// Free a node that has already been unlinked, returning its value.
+        ///
+        /// # Safety
+        ///
+        /// `node` must have come from [`Node::new`], and must not be used again (by the list or anything else).
+        unsafe fn into_value(node: NonNull<Self>) -> T {
+            // SAFETY: the caller guarantees that `node` is a leaked `MyBox` that is never used again
+            unsafe { MyBox::from_raw(node.as_ptr()) }.into_inner().value
+        }
+    }
+
+    /// Doubly linked list of values, which can be pushed and popped at either end.
+    ///
+    /// Like [`alloc::collections::LinkedList`], every value is in its own heap node.
+    #[doc(alias = "MyLinkedList")]
+    pub struct MyDoublyLinkedList<T> {
+        /*
+        invariants:
+        - `head` and `tail` are both `None` (and `len == 0`), or both point to nodes owned by this list
+        - following `next` from `head` visits exactly `len` nodes and ends at `tail`, and following `prev` from `tail`
+          visits the same nodes in reverse (so `node.next.prev == node` and `node.prev.next == node` wherever they exist)
+        - `head.prev` and `tail.next` are `None`
+        */
+        head: Link<T>,
+        tail: Link<T>,
+        len: usize,
+        // tells the drop checker that the list owns its nodes (and so their values)
+        _owns: PhantomData<MyBox<Node<T>>>,
+    }
+
+    impl<T> MyDoublyLinkedList<T> {
+        /// Create an empty list, without allocating.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                head: None,
+                tail: None,
+                len: 0,
+                _owns: PhantomData,
+            }
+        }
+
+        /// Number of values in the list, in O(1).
+        #[doc(alias = "size")]
+        #[inline]
+        pub const fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Add `value` to the front of the list.
+        pub fn push_front(&mut self, value: T) {
+            // SAFETY: `head` (if any) is owned by this list, and is the first node
+            unsafe { self.link_between(Node::new(value), None, self.head) };
+        }
+
+        /// Add `value` to the back of the list.
+        pub fn push_back(&mut self, value: T) {
+            // SAFETY: `tail` (if any) is owned by this list, and is the last node
+            unsafe { self.link_between(Node::new(value), self.tail, None) };
+        }
+
+        /// Remove the first value from the list and return it, or `None` if it is empty.
+        pub fn pop_front(&mut self) -> Option<T> {
+            // SAFETY: `head` is owned by this list
+            self.head.map(|head| unsafe { self.unlink(head) })
+        }
+
+        /// Remove the last value from the list and return it, or `None` if it is empty.
+        pub fn pop_back(&mut self) -> Option<T> {
+            // SAFETY: `tail` is owned by this list
+            self.tail.map(|tail| unsafe { self.unlink(tail) })
+        }
+
+        /// Link the new `node` in between `prev` and `next`, where `None` means the front (for `prev`) or back (for `next`).
+        ///
+        /// # Safety
+        ///
+        /// `node` must have come from [`Node::new`] and not be linked anywhere yet, and `prev` and `next` must be owned by
+        /// this list, and be adjacent (or the first/last node, or both `None` for an empty list).
+        unsafe fn link_between(&mut self, node: NonNull<Node<T>>, prev: Link<T>, next: Link<T>) {
+            // SAFETY: the caller guarantees that every node is valid, and the list is mutably borrowed
+            unsafe {
+                (*node.as_ptr()).prev = prev;
+                (*node.as_ptr()).next = next;
+                match prev {
+                    Some(prev) => (*prev.as_ptr()).next = Some(node),
+                    None => self.head = Some(node),
+                }
+                match next {
+                    Some(next) => (*next.as_ptr()).prev = Some(node),
+                    None => self.tail = Some(node),
+                }
+            }
+            self.len += 1;
+        }
+
+        /// Unlink `node` from its neighbors (or the ends of the list), free it, and return its value.
+        ///
+        /// # Safety
+        ///
+        /// `node` must be owned by this list, and is freed by this call, so it must not be used again.
+        unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+            // SAFETY: the caller guarantees that `node` is owned by this list (and so are its neighbors), which is
+            // mutably borrowed; it is unlinked before being freed, so nothing refers to it afterwards
+            unsafe {
+                let Node { prev, next, .. } = *node.as_ptr();
+                match prev {
+                    Some(prev) => (*prev.as_ptr()).next = next,
+                    None => self.head = next,
+                }
+                match next {
+                    Some(next) => (*next.as_ptr()).prev = prev,
+                    None => self.tail = prev,
+                }
+                self.len -= 1;
+                Node::into_value(node)
+            }
+        }
+
+        /// Move every node of `other` in between `prev` and `next`, in order, without allocating.
+        ///
+        /// # Safety
+        ///
+        /// `prev` and `next` must be owned by this list, and be adjacent (or the first/last node, or both `None` for an
+        /// empty list).
+        unsafe fn splice_between(&mut self, mut other: Self, prev: Link<T>, next: Link<T>) {
+            // taking the ends leaves `other` to drop as an empty list
+            let (Some(first), Some(last)) = (other.head.take(), other.tail.take()) else {
+                return;
+            };
+            // SAFETY: `first` and `last` are the ends of `other`, whose nodes now belong to this list, and the caller
+            // guarantees that `prev` and `next` are valid and adjacent; the list is mutably borrowed
+            unsafe {
+                (*first.as_ptr()).prev = prev;
+                (*last.as_ptr()).next = next;
+                match prev {
+                    Some(prev) => (*prev.as_ptr()).next = Some(first),
+                    None => self.head = Some(first),
+                }
+                match next {
+                    Some(next) => (*next.as_ptr()).prev = Some(last),
+                    None => self.tail = Some(last),
+                }
+            }
+            self.len += mem::take(&mut other.len);
+        }
+
+        /// Move every value from `other` onto the back of this list, in O(1).
+        ///
+        /// `other` is left empty.
+        pub fn append(&mut self, other: &mut Self) {
+            // SAFETY: `tail` (if any) is owned by this list, and is the last node
+            unsafe { self.splice_between(mem::take(other), self.tail, None) };
+        }
+
+        /// Split the list in two, returning everything from index `at` onwards (and keeping the first `at` values).
+        ///
+        /// This is O(min(at, len - at)), since the split point is found by walking from the nearer end.
+        ///
+        /// # Panics
+        ///
+        /// If `at > len`.
+        #[track_caller]
+        pub fn split_off(&mut self, at: usize) -> Self {
+            let len = self.len;
+            assert!(
+                at <= len,
+                "`at` split index (is {at}) should be <= len (is {len})"
+            );
+            if at == 0 {
+                return mem::take(self);
+            }
+            // the last node that stays in this list
+            let last = if at <= len / 2 {
+                let mut node = self.head;
+                for _ in 1..at {
+                    // SAFETY: there are at least `at` nodes, so every node walked over is owned by this list
+                    node = node.and_then(|node| unsafe { (*node.as_ptr()).next });
+                }
+                node
+            } else {
+                let mut node = self.tail;
+                for _ in at..len {
+                    // SAFETY: there are more than `len - at` nodes, so every node walked over is owned by this list
+                    node = node.and_then(|node| unsafe { (*node.as_ptr()).prev });
+                }
+                node
+            };
+            // SAFETY: `0 < at <= len`, so `last` is a node owned by this list, and the nodes after it (if any) are moved
+            // into the new list, whose ends are detached from this one
+            unsafe {
+                let last = last.unwrap_unchecked();
+                let first = (*last.as_ptr()).next.take();
+                if let Some(first) = first {
+                    (*first.as_ptr()).prev = None;
+                }
+                let tail = self.tail.replace(last);
+                self.len = at;
+                Self {
+                    head: first,
+                    tail: first.and(tail),
+                    len: len - at,
+                    _owns: PhantomData,
+                }
+            }
+        }
+
+        /// Get a reference to the first value, or `None` if the list is empty.
+        #[inline]
+        pub fn front(&self) -> Option<&T> {
+            // SAFETY: `head` is owned by this list, and the reference borrows the list
+            self.head.map(|head| unsafe { &(*head.as_ptr()).value })
+        }
+
+        /// Get a mutable reference to the first value, or `None` if the list is empty.
+        #[inline]
+        pub fn front_mut(&mut self) -> Option<&mut T> {
+            // SAFETY: `head` is owned by this list, and the reference mutably borrows the list
+            self.head.map(|head| unsafe { &mut (*head.as_ptr()).value })
+        }
+
+        /// Get a reference to the last value, or `None` if the list is empty.
+        #[inline]
+        pub fn back(&self) -> Option<&T> {
+            // SAFETY: `tail` is owned by this list, and the reference borrows the list
+            self.tail.map(|tail| unsafe { &(*tail.as_ptr()).value })
+        }
+
+        /// Get a mutable reference to the last value, or `None` if the list is empty.
+        #[inline]
+        pub fn back_mut(&mut self) -> Option<&mut T> {
+            // SAFETY: `tail` is owned by this list, and the reference mutably borrows the list
+            self.tail.map(|tail| unsafe { &mut (*tail.as_ptr()).value })
+        }
+
+        /// Drop every value in the list, freeing every node.
+        pub fn clear(&mut self) {
+            // detach the nodes first, so that a panicking destructor leaves an empty (rather than dangling) list behind;
+            // the nodes after the panicking one are leaked, but never double-dropped
+            let mut link = self.head.take();
+            self.tail = None;
+            self.len = 0;
+            while let Some(node) = link {
+                // SAFETY: the detached nodes are only reachable from here, and each one is freed exactly once
+                unsafe {
+                    link = (*node.as_ptr()).next;
+                    drop(Node::into_value(node));
+                }
+            }
+        }
+
+        /// Iterate over references to the values, from front to back (or back to front, with [`rev`](Iterator::rev)).
+        #[inline]
+        pub fn iter(&self) -> Iter<'_, T> {
+            Iter {
+                head: self.head,
+                tail: self.tail,
+                len: self.len,
+                _borrows: PhantomData,
+            }
+        }
+
+        /// Iterate over mutable references to the values, from front to back (or back to front, with [`rev`](Iterator::rev)).
+        #[inline]
+        pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+            IterMut {
+                head: self.head,
+                tail: self.tail,
+                len: self.len,
+                _borrows: PhantomData,
+            }
+        }
+
+        /// Create a cursor at the first value (or at the "ghost" position, if the list is empty).
+        #[inline]
+        pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+            CursorMut {
+                current: self.head,
+                index: 0,
+                list: self,
+            }
+        }
+
+        /// Create a cursor at the last value (or at the "ghost" position, if the list is empty).
+        #[inline]
+        pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+            CursorMut {
+                current: self.tail,
+                index: self.len.saturating_sub(1),
+                list: self,
+            }
+        }
+
+        /// Walk the list in both directions, checking every invariant of the links.
+        ///
+        /// # Panics
+        ///
+        /// If any invariant is broken.
+        #[cfg(test)]
+        pub(super) fn assert_invariants(&self) {
+            assert_eq!(
+                self.head.is_none(),
+                self.tail.is_none(),
+                "only one end is set"
+            );
+            let mut forward = std::vec::Vec::new();
+            let mut prev = None;
+            let mut link = self.head;
+            while let Some(node) = link {
+                assert!(forward.len() < self.len, "more nodes than `len`");
+                // SAFETY: every linked node is owned by this list
+                let node_ref = unsafe { node.as_ref() };
+                assert_eq!(node_ref.prev, prev, "`prev` doesn't match the node before");
+                forward.push(node);
+                prev = Some(node);
+                link = node_ref.next;
+            }
+            assert_eq!(prev, self.tail, "walking forward doesn't end at `tail`");
+
+            let mut backward = std::vec::Vec::new();
+            let mut next = None;
+            let mut link = self.tail;
+            while let Some(node) = link {
+                assert!(backward.len() < self.len, "more nodes than `len`");
+                // SAFETY: every linked node is owned by this list
+                let node_ref = unsafe { node.as_ref() };
+                assert_eq!(node_ref.next, next, "`next` doesn't match the node after");
+                backward.push(node);
+                next = Some(node);
+                link = node_ref.prev;
+            }
+            assert_eq!(next, self.head, "walking backward doesn't end at `head`");
+
+            backward.reverse();
+            assert_eq!(forward.len(), self.len, "fewer nodes than `len`");
+            assert_eq!(
+                forward, backward,
+                "walking forward and backward visit different nodes"
+            );
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Nodes are freed one at a time, so even very long lists can't overflow the stack.
+    impl<T> Drop for MyDoublyLinkedList<T> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    // SAFETY: the list uniquely owns its nodes (like `MyBox<Node<T>>`s), so it is as thread-safe as `T`
+    unsafe impl<T: Send> Send for MyDoublyLinkedList<T> {}
+
+    // SAFETY: `&MyDoublyLinkedList` only gives out `&T`, so sharing the list is equivalent to sharing the values
+    unsafe impl<T: Sync> Sync for MyDoublyLinkedList<T> {}
+
+    /// Cursor over a [`MyDoublyLinkedList`], created by [`MyDoublyLinkedList::cursor_front_mut`] or [`MyDoublyLinkedList::cursor_back_mut`].
+    ///
+    /// The cursor points at a value, or at a "ghost" position between the back and the front of the list.
+    /// Moving past either end lands on the ghost, and moving on from the ghost wraps around to the other end,
+    /// which makes it possible to insert values at either end through the cursor.
+    pub struct CursorMut<'a, T> {
+        // invariant: `current` is `None` (the ghost, with `index == list.len`) or a node owned by `list`, at `index`
+        current: Link<T>,
+        index: usize,
+        list: &'a mut MyDoublyLinkedList<T>,
+    }
+
+    impl<T> CursorMut<'_, T> {
+        /// Position of the current value in the list, or `None` at the ghost position.
+        #[inline]
+        pub fn index(&self) -> Option<usize> {
+            self.current.map(|_| self.index)
+        }
+
+        /// Move to the next value, from the back onto the ghost, or from the ghost onto the front.
+        pub fn move_next(&mut self) {
+            match self.current {
+                Some(node) => {
+                    // SAFETY: `current` is owned by the list
+                    self.current = unsafe { (*node.as_ptr()).next };
+                    self.index += 1;
+                }
+                None => {
+                    self.current = self.list.head;
+                    self.index = 0;
+                }
+            }
+        }
+
+        /// Move to the previous value, from the front onto the ghost, or from the ghost onto the back.
+        pub fn move_prev(&mut self) {
+            match self.current {
+                Some(node) => {
+                    // SAFETY: `current` is owned by the list
+                    self.current = unsafe { (*node.as_ptr()).prev };
+                    self.index = self.index.checked_sub(1).unwrap_or(self.list.len);
+                }
+                None => {
+                    self.current = self.list.tail;
+                    self.index = self.list.len.saturating_sub(1);
+                }
+            }
+        }
+
+        /// Get a mutable reference to the current value, or `None` at the ghost position.
+        #[inline]
+        pub fn current(&mut self) -> Option<&mut T> {
+            // SAFETY: `current` is owned by the list, and the reference mutably borrows the cursor (and so the list)
+            self.current
+                .map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
+        /// Get a mutable reference to the next value (the front, at the ghost position), or `None` at the back.
+        pub fn peek_next(&mut self) -> Option<&mut T> {
+            let next = match self.current {
+                // SAFETY: `current` is owned by the list
+                Some(node) => unsafe { (*node.as_ptr()).next },
+                None => self.list.head,
+            };
+            // SAFETY: `next` is owned by the list, and the reference mutably borrows the cursor (and so the list)
+            next.map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
+        /// Get a mutable reference to the previous value (the back, at the ghost position), or `None` at the front.
+        pub fn peek_prev(&mut self) -> Option<&mut T> {
+            let prev = match self.current {
+                // SAFETY: `current` is owned by the list
+                Some(node) => unsafe { (*node.as_ptr()).prev },
+                None => self.list.tail,
+            };
+            // SAFETY: `prev` is owned by the list, and the reference mutably borrows the cursor (and so the list)
+            prev.map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
+        /// Insert `value` right after the current value (at the front, at the ghost position), without moving the cursor.
+        pub fn insert_after(&mut self, value: T) {
+            let node = Node::new(value);
+            match self.current {
+                // SAFETY: `current` and its `next` are adjacent nodes owned by the list
+                Some(current) => unsafe {
+                    self.list
+                        .link_between(node, Some(current), (*current.as_ptr()).next);
+                },
+                None => {
+                    // SAFETY: the front of the list is owned by the list
+                    unsafe { self.list.link_between(node, None, self.list.head) };
+                    // the ghost stays at the end
+                    self.index = self.list.len;
+                }
+            }
+        }
+
+        /// Insert `value` right before the current value (at the back, at the ghost position), without moving the cursor.
+        pub fn insert_before(&mut self, value: T) {
+            let node = Node::new(value);
+            match self.current {
+                // SAFETY: `current` and its `prev` are adjacent nodes owned by the list
+                Some(current) => unsafe {
+                    self.list
+                        .link_between(node, (*current.as_ptr()).prev, Some(current));
+                },
+                // SAFETY: the back of the list is owned by the list
+                None => unsafe { self.list.link_between(node, self.list.tail, None) },
+            }
+            // either way, there is one more value before the cursor
+            self.index += 1;
+        }
+
+        /// Move every value of `other` in right after the current value (at the front, at the ghost position), in O(1).
+        ///
+        /// The cursor stays on the same value; no nodes are allocated or freed.
+        pub fn splice_after(&mut self, other: MyDoublyLinkedList<T>) {
+            match self.current {
+                // SAFETY: `current` and its `next` are adjacent nodes owned by the list
+                Some(current) => unsafe {
+                    self.list
+                        .splice_between(other, Some(current), (*current.as_ptr()).next);
+                },
+                None => {
+                    // SAFETY: the front of the list is owned by the list
+                    unsafe { self.list.splice_between(other, None, self.list.head) };
+                    // the ghost stays at the end
+                    self.index = self.list.len;
+                }
+            }
+        }
+
+        /// Remove the current value and return it, moving the cursor to the value after it (or the ghost).
+        ///
+        /// Returns `None` (and does nothing) at the ghost position.
+        pub fn remove_current(&mut self) -> Option<T> {
+            let node = self.current?;
+            // SAFETY: `current` is owned by the list, and the cursor moves off it before it is freed
+            unsafe {
+                self.current = (*node.as_ptr()).next;
+                Some(self.list.unlink(node))
+            }
+        }
+    }
+
+    /// Iterator over references to the values of a [`MyDoublyLinkedList`], created by [`MyDoublyLinkedList::iter`].
+    pub struct Iter<'a, T> {
+        // invariant: the `len` nodes from `head` to `tail` (inclusive) haven't been yielded yet, and are borrowed from the list
+        head: Link<T>,
+        tail: Link<T>,
+        len: usize,
+        _borrows: PhantomData<&'a T>,
+    }
+
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<&'a T> {
+            if self.len == 0 {
+                return None;
+            }
+            let node = self.head?;
+            // SAFETY: the node hasn't been yielded, and is borrowed from the list for `'a`
+            let node = unsafe { &*node.as_ptr() };
+            self.head = node.next;
+            self.len -= 1;
+            Some(&node.value)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
+        }
+    }
+
+    impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+        fn next_back(&mut self) -> Option<&'a T> {
+            if self.len == 0 {
+                return None;
+            }
+            let node = self.tail?;
+            // SAFETY: the node hasn't been yielded, and is borrowed from the list for `'a`
+            let node = unsafe { &*node.as_ptr() };
+            self.tail = node.prev;
+            self.len -= 1;
+            Some(&node.value)
+        }
+    }
+
+    /// Translation of the copy constructor into Rust.
+    impl<T> Clone for Iter<'_, T> {
+        fn clone(&self) -> Self {
+            Self { ..*self }
+        }
+    }
+
+    // SAFETY: the iterator only gives out `&T`, just like `&MyDoublyLinkedList<T>`
+    unsafe impl<T: Sync> Send for Iter<'_, T> {}
+
+    // SAFETY: the iterator only gives out `&T`, just like `&MyDoublyLinkedList<T>`
+    unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+    /// Iterator over mutable references to the values of a [`MyDoublyLinkedList`], created by [`MyDoublyLinkedList::iter_mut`].
+    pub struct IterMut<'a, T> {
+        // invariant: the `len` nodes from `head` to `tail` (inclusive) haven't been yielded yet, and are mutably
+        // borrowed from the list, so each value is handed out at most once
+        head: Link<T>,
+        tail: Link<T>,
+        len: usize,
+        _borrows: PhantomData<&'a mut T>,
+    }
+
+    impl<T> IterMut<'_, T> {
+        /// Rust-specific helper to look at the remaining values without consuming them.
+        pub(super) fn as_iter(&self) -> Iter<'_, T> {
+            Iter {
+                head: self.head,
+                tail: self.tail,
+                len: self.len,
+                _borrows: PhantomData,
+            }
+        }
+    }
+
+    impl<'a, T> Iterator for IterMut<'a, T> {
+        type Item = &'a mut T;
+
+        fn next(&mut self) -> Option<&'a mut T> {
+            if self.len == 0 {
+                return None;
+            }
+            let node = self.head?;
+            // SAFETY: the node hasn't been yielded (and won't be again), and is mutably borrowed from the list for `'a`
+            let node = unsafe { &mut *node.as_ptr() };
+            self.head = node.next;
+            self.len -= 1;
+            Some(&mut node.value)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
+        }
+    }
+
+    impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+        fn next_back(&mut self) -> Option<&'a mut T> {
+            if self.len == 0 {
+                return None;
+            }
+            let node = self.tail?;
+            // SAFETY: the node hasn't been yielded (and won't be again), and is mutably borrowed from the list for `'a`
+            let node = unsafe { &mut *node.as_ptr() };
+            self.tail = node.prev;
+            self.len -= 1;
+            Some(&mut node.value)
+        }
+    }
+
+    // SAFETY: the iterator only gives out `&mut T`, just like `&mut MyDoublyLinkedList<T>`
+    unsafe impl<T: Send> Send for IterMut<'_, T> {}
+
+    // SAFETY: `&IterMut` only gives out `&T` (through `as_iter`), just like `&MyDoublyLinkedList<T>`
+    unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+}
+
+impl<T> MyDoublyLinkedList<T> {
+    /// Check whether the list has no values.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T> Default for MyDoublyLinkedList<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+impl<T: Clone> Clone for MyDoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way, from front to back.
+impl<T: fmt::Debug> fmt::Debug for MyDoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Translation of `operator==`: lists are equal if they hold equal values in the same order.
+impl<T: PartialEq> PartialEq for MyDoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for MyDoublyLinkedList<T> {}
+
+/// Translation of `operator<` and friends: lists are compared lexicographically, from front to back.
+impl<T: PartialOrd> PartialOrd for MyDoublyLinkedList<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord> Ord for MyDoublyLinkedList<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+/// Rust-specific helper to hash lists by their values, consistent with [`PartialEq`].
+///
+/// The length is hashed first, so that nested lists like `[[1], [2]]` and `[[1, 2]]` hash differently.
+impl<T: Hash> Hash for MyDoublyLinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|value| value.hash(state));
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&DebugValues(self.clone()))
+            .finish()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IterMut")
+            .field(&DebugValues(self.as_iter()))
+            .finish()
+    }
+}
+
+/// Owning iterator over the values of a [`MyDoublyLinkedList`], created by [`MyDoublyLinkedList::into_iter`](IntoIterator::into_iter).
+///
+/// Any values that weren't yielded are dropped (along with their nodes) when the iterator is dropped.
+pub struct IntoIter<T> {
+    list: MyDoublyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.list).finish()
+    }
+}
+
+/// Rust-specific helper to move the values out of a list, from front to back (or back to front, with [`rev`](Iterator::rev)).
+impl<T> IntoIterator for MyDoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+/// Rust-specific helper to iterate over a list by reference, as in `for value in &list`.
+impl<'a, T> IntoIterator for &'a MyDoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Rust-specific helper to iterate over a list by mutable reference, as in `for value in &mut list`.
+impl<'a, T> IntoIterator for &'a mut MyDoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Rust-specific helper to allow collecting an iterator into a list, keeping the iterator's order.
+impl<T> FromIterator<T> for MyDoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Rust-specific helper to append every value from an iterator to the back of the list, keeping the iterator's order.
+impl<T> Extend<T> for MyDoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push_back(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_drop::{CountDrops, check_drops_once},
+        test_rng::TestRng,
+        test_std::to_std,
+    };
+
+    #[test]
+    fn push_pop_both_ends() {
+        let mut list = MyDoublyLinkedList::new();
+        list.assert_invariants();
+        assert_eq!((list.pop_front(), list.pop_back()), (None, None));
+
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        list.push_front(0);
+        list.assert_invariants();
+        assert_eq!(to_std(&list), [0, 1, 2, 3]);
+        assert_eq!((list.front(), list.back()), (Some(&0), Some(&3)));
+
+        assert_eq!(list.pop_back(), Some(3));
+        list.assert_invariants();
+        assert_eq!(list.pop_front(), Some(0));
+        list.assert_invariants();
+        list.push_back(4);
+        assert_eq!(to_std(&list), [1, 2, 4]);
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_back(), Some(2));
+        list.assert_invariants();
+        assert!(list.is_empty());
+        assert_eq!((list.front(), list.back()), (None, None));
+    }
+
+    #[test]
+    fn interleaved_against_std() {
+        let mut list = MyDoublyLinkedList::new();
+        let mut expected = std::collections::VecDeque::new();
+        // a fixed pseudo-random sequence of operations, biased towards growing the list
+        let mut rng = TestRng::new(12_345);
+        for i in 0..500 {
+            match rng.below(5) {
+                0 => {
+                    list.push_front(i);
+                    expected.push_front(i);
+                }
+                1 | 2 => {
+                    list.push_back(i);
+                    expected.push_back(i);
+                }
+                3 => assert_eq!(list.pop_front(), expected.pop_front()),
+                _ => assert_eq!(list.pop_back(), expected.pop_back()),
+            }
+            assert_eq!(list.len(), expected.len());
+        }
+        list.assert_invariants();
+        assert!(list.iter().eq(&expected));
+        assert!(list.iter().rev().eq(expected.iter().rev()));
+    }
+
+    #[test]
+    fn front_back_mut() {
+        let mut list = MyDoublyLinkedList::new();
+        list.push_back(std::string::String::from("a"));
+        list.front_mut().unwrap().push('!');
+        list.back_mut().unwrap().push('?');
+        assert_eq!(list.front().map(|s| s.as_str()), Some("a!?"));
+        list.push_back(std::string::String::from("b"));
+        list.back_mut().unwrap().push('.');
+        assert_eq!(std::format!("{list:?}"), r#"["a!?", "b."]"#);
+    }
+
+    #[test]
+    fn clone_and_debug() {
+        let mut list = MyDoublyLinkedList::new();
+        for i in 0..4 {
+            list.push_back(i);
+        }
+        let mut clone = list.clone();
+        clone.assert_invariants();
+        clone.pop_front();
+        *clone.back_mut().unwrap() = 30;
+        assert_eq!(std::format!("{list:?}"), "[0, 1, 2, 3]");
+        assert_eq!(std::format!("{clone:?}"), "[1, 2, 30]");
+
+        let mut iter = list.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(&0), Some(&3)));
+        assert_eq!(std::format!("{iter:?}"), "Iter([1, 2])");
+        assert_eq!(iter.len(), 2);
+        let mut iter_mut = list.iter_mut();
+        iter_mut.next_back();
+        assert_eq!(std::format!("{iter_mut:?}"), "IterMut([0, 1, 2])");
+        assert_eq!(
+            std::format!("{:?}", clone.into_iter()),
+            "IntoIter([1, 2, 30])"
+        );
+    }
+
+    #[test]
+    fn drops_each_value_once() {
+        let drops = core::cell::Cell::new(0);
+        let popped = check_drops_once(
+            &drops,
+            MyDoublyLinkedList::new(),
+            // odd values at the front and even ones at the back, so both ends get relinked
+            |list, value| {
+                if value.value % 2 == 0 {
+                    list.push_back(value);
+                } else {
+                    list.push_front(value);
+                }
+            },
+            MyDoublyLinkedList::pop_back,
+            |list| {
+                list.clear();
+                list.assert_invariants();
+            },
+        );
+        assert_eq!(popped, 8);
+    }
+
+    #[test]
+    fn cursor_lru_reordering() {
+        /// Move `key` to the front (most recently used), or insert it there, evicting from the back past `capacity`.
+        fn touch(list: &mut MyDoublyLinkedList<char>, key: char, capacity: usize) {
+            let mut cursor = list.cursor_front_mut();
+            while let Some(&mut current) = cursor.current() {
+                if current == key {
+                    assert_eq!(cursor.remove_current(), Some(key));
+                    break;
+                }
+                cursor.move_next();
+            }
+            list.assert_invariants();
+            // before the front value (or, in an empty list, before the ghost) is the new front
+            let mut cursor = list.cursor_front_mut();
+            cursor.insert_before(key);
+            assert_eq!(cursor.peek_prev().copied(), Some(key));
+            list.assert_invariants();
+            if list.len() > capacity {
+                let mut cursor = list.cursor_back_mut();
+                cursor.remove_current();
+                assert_eq!(cursor.index(), None);
+                list.assert_invariants();
+            }
+        }
+
+        let mut list = MyDoublyLinkedList::new();
+        for key in "abcacdbe".chars() {
+            touch(&mut list, key, 3);
+        }
+        assert_eq!(to_std(&list), ['e', 'b', 'd']);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn cursor_ghost_wraps() {
+        let mut list = MyDoublyLinkedList::new();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.remove_current(), None);
+        // at the ghost, inserting after means at the front, and before means at the back
+        cursor.insert_after(1);
+        cursor.insert_before(2);
+        cursor.insert_after(0);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 0)));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 2)));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        list.assert_invariants();
+        assert_eq!(to_std(&list), [0, 1, 2]);
+
+        // inserting around the current value keeps track of its index
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_before(10);
+        assert_eq!((cursor.index(), cursor.current()), (Some(3), Some(&mut 2)));
+        cursor.insert_after(20);
+        assert_eq!(cursor.peek_next(), Some(&mut 20));
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(10));
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 2)));
+        list.assert_invariants();
+        assert_eq!(to_std(&list), [0, 1, 2, 20]);
+
+        // removing the last value moves onto the ghost
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(20));
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        list.assert_invariants();
+        assert_eq!(
+            MyDoublyLinkedList::<i32>::new().cursor_back_mut().index(),
+            None
+        );
+    }
+
+    #[test]
+    fn split_off_and_append() {
+        let values: std::vec::Vec<i32> = (0..9).collect();
+        for at in [0, 1, 3, 4, 5, 8, 9] {
+            let mut list: MyDoublyLinkedList<i32> = MyDoublyLinkedList::new();
+            values.iter().for_each(|&value| list.push_back(value));
+            let mut back = list.split_off(at);
+            list.assert_invariants();
+            back.assert_invariants();
+            assert_eq!(to_std(&list), values[..at]);
+            assert_eq!(to_std(&back), values[at..]);
+
+            list.append(&mut back);
+            list.assert_invariants();
+            back.assert_invariants();
+            assert_eq!(to_std(&list), values);
+            assert!(back.is_empty());
+        }
+
+        // appending onto an empty list takes the whole other list
+        let mut empty = MyDoublyLinkedList::new();
+        let mut list = MyDoublyLinkedList::new();
+        list.push_back('a');
+        empty.append(&mut list);
+        empty.assert_invariants();
+        assert_eq!((to_std(&empty), list.len()), (std::vec!['a'], 0));
+        assert!(MyDoublyLinkedList::<char>::new().split_off(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic = "`at` split index (is 3) should be <= len (is 2)"]
+    fn split_off_past_end() {
+        let mut list = MyDoublyLinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.split_off(3);
+    }
+
+    #[test]
+    fn cursor_splice_after() {
+        use crate::test_alloc::count_allocations;
+
+        fn list_of(values: &[i32]) -> MyDoublyLinkedList<i32> {
+            let mut list = MyDoublyLinkedList::new();
+            values.iter().for_each(|&value| list.push_back(value));
+            list
+        }
+
+        let mut list = list_of(&[1, 5]);
+        let mut cursor = list.cursor_front_mut();
+        let other = list_of(&[2, 3, 4]);
+        let ((), allocations) = count_allocations(|| cursor.splice_after(other));
+        assert_eq!(allocations, 0);
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 1)));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+
+        // at the back, and at the ghost (which means the front)
+        cursor.move_prev();
+        cursor.splice_after(list_of(&[-1, 0]));
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        cursor.splice_after(list_of(&[6]));
+        cursor.splice_after(MyDoublyLinkedList::new());
+        assert_eq!((cursor.index(), cursor.current()), (Some(6), Some(&mut 5)));
+        list.assert_invariants();
+        assert_eq!(to_std(&list), [-1, 0, 1, 2, 3, 4, 5, 6]);
+
+        let mut empty = MyDoublyLinkedList::new();
+        empty.cursor_back_mut().splice_after(list_of(&[7, 8]));
+        empty.assert_invariants();
+        assert_eq!(to_std(&empty), [7, 8]);
+    }
+
+    #[test]
+    fn iterators_meet_in_the_middle() {
+        for len in [0, 1, 2, 5, 6] {
+            let mut list: MyDoublyLinkedList<usize> = (0..len).collect();
+            let expected: std::collections::VecDeque<usize> = (0..len).collect();
+
+            // take turns from the front and back, until both sides meet
+            let mut iter = list.iter();
+            let mut expected_iter = expected.iter();
+            for step in 0.. {
+                assert_eq!(iter.len(), expected_iter.len());
+                let (value, expected_value) = if step % 2 == 0 {
+                    (iter.next(), expected_iter.next())
+                } else {
+                    (iter.next_back(), expected_iter.next_back())
+                };
+                assert_eq!(value, expected_value);
+                if value.is_none() {
+                    break;
+                }
+            }
+            // stays exhausted from both ends
+            assert_eq!((iter.next(), iter.next_back(), iter.len()), (None, None, 0));
+
+            let mut iter_mut = list.iter_mut();
+            while let (Some(front), back) = (iter_mut.next(), iter_mut.next_back()) {
+                *front += 100;
+                back.into_iter().for_each(|back| *back += 200);
+            }
+            assert_eq!((iter_mut.next(), iter_mut.next_back()), (None, None));
+            list.assert_invariants();
+            let shifted: std::vec::Vec<usize> = (0..len)
+                .map(|i| i + if i < len.div_ceil(2) { 100 } else { 200 })
+                .collect();
+            assert_eq!(to_std(&list), shifted);
+
+            let mut into_iter = list.into_iter();
+            let mut yielded = std::vec::Vec::new();
+            while let Some(front) = into_iter.next() {
+                yielded.push(front);
+                yielded.extend(into_iter.next_back());
+            }
+            assert_eq!((into_iter.next(), into_iter.next_back()), (None, None));
+            assert_eq!(yielded.len(), len);
+        }
+    }
+
+    #[test]
+    fn into_iter_drops_rest() {
+        let drops = core::cell::Cell::new(0);
+        let list: MyDoublyLinkedList<_> = (0..5).map(|i| CountDrops::new(i, &drops)).collect();
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next_back().map(|value| value.value), Some(4));
+        assert_eq!(drops.get(), 1);
+        assert_eq!(std::format!("{iter:?}").matches("CountDrops").count(), 4);
+        drop(iter);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn compare_and_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let forward: MyDoublyLinkedList<_> = (1..=4).collect();
+        let mut backward = MyDoublyLinkedList::new();
+        for value in (1..=4).rev() {
+            backward.push_front(value);
+        }
+        assert_eq!(forward, backward);
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&forward), state.hash_one(&backward));
+
+        let mut longer = forward.clone();
+        longer.extend([5]);
+        assert_ne!(forward, longer);
+        assert!(forward < longer);
+        let mut bigger = forward.clone();
+        *bigger.front_mut().unwrap() = 2;
+        assert_eq!(longer.cmp(&bigger), Ordering::Less);
+        assert!(MyDoublyLinkedList::new() < forward);
+
+        let nested: MyDoublyLinkedList<MyDoublyLinkedList<i32>> =
+            [(1..=1).collect(), (2..=2).collect()].into_iter().collect();
+        let flat: MyDoublyLinkedList<MyDoublyLinkedList<i32>> =
+            [(1..=2).collect()].into_iter().collect();
+        assert_ne!(state.hash_one(&nested), state.hash_one(&flat));
+        let nan: MyDoublyLinkedList<f64> = [f64::NAN].into_iter().collect();
+        assert_eq!(nan.partial_cmp(&nan), None);
+    }
+
+    #[test]
+    fn long_list_drop() {
+        // `clear` unlinks from the front through the raw `next` pointers in a loop, so even a long list is freed in
+        // constant stack space; make sure every node is reached (Miri is too slow for that many nodes)
+        let len = if cfg!(miri) { 1_000 } else { 200_000 };
+        let mut list = MyDoublyLinkedList::new();
+        for i in 0..len {
+            list.push_back(i);
+        }
+        assert_eq!(list.len(), len);
+        assert_eq!(list.back(), Some(&(len - 1)));
+        core::mem::drop(list);
+    }
+}
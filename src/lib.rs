@@ -12,6 +12,18 @@
 //!     - When this happens, the trait's main function will have a `doc::alias` attribute applied, to make it appear in search.
 
 #![no_std]
+#![cfg_attr(
+    feature = "nightly",
+    feature(
+        coerce_unsized,
+        dispatch_from_dyn,
+        fn_traits,
+        tuple_trait,
+        unboxed_closures,
+        unsize
+    )
+)]
+#![cfg_attr(all(test, feature = "nightly"), feature(arbitrary_self_types))]
 #![warn(missing_docs)]
 #![deny(
     unsafe_code,
@@ -23,4 +35,27 @@ extern crate alloc;
 #[cfg(test)] // during tests, allow std for print and such
 extern crate std;
 
+pub mod allocator;
+pub mod array_stack;
+pub mod binary_heap;
+pub mod d_ary_heap;
+pub mod doubly_linked_list;
+pub mod min_stack;
 pub mod my_box;
+pub mod my_deque;
+pub mod my_queue;
+pub mod my_small_box;
+pub mod my_vec;
+pub mod singly_linked_list;
+
+mod debug_values;
+mod raw_buf;
+
+#[cfg(test)]
+mod test_alloc;
+#[cfg(test)]
+mod test_drop;
+#[cfg(test)]
+mod test_rng;
+#[cfg(test)]
+mod test_std;
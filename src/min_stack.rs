@@ -0,0 +1,223 @@
+//! Stack data structure that can report its minimum value in O(1).
+//!
+//! Alongside the values, the stack keeps the positions of every value that was a new minimum when it was pushed.
+//! Since values can only leave from the top, the last of those positions is always the current minimum.
+
+use crate::my_vec::MyVec;
+use core::{cmp::Ordering, fmt};
+
+/// Stack that tracks its least value according to an arbitrary order `F`, such as a maximum (with a reversed order).
+///
+/// Every operation is O(1) (amortized, for [`push`](Self::push)).
+///
+/// ```
+/// # use datastructures_and_algorithms::min_stack::ExtremumStack;
+/// let mut max_stack = ExtremumStack::with_order(|a: &i32, b: &i32| b.cmp(a));
+/// max_stack.push(3);
+/// max_stack.push(7);
+/// max_stack.push(5);
+/// assert_eq!(max_stack.extremum(), Some(&7));
+/// ```
+pub struct ExtremumStack<T, F> {
+    values: MyVec<T>,
+    // invariant: strictly increasing indices into `values`, of every value that was no greater (by `order`) than
+    // the previous extremum when it was pushed, so the last one is the position of the current extremum
+    extrema: MyVec<usize>,
+    order: F,
+}
+
+/// Stack that tracks its minimum value, as an [`ExtremumStack`] ordered by [`Ord`].
+///
+/// To track the maximum instead, use [`core::cmp::Reverse`] values, or [`ExtremumStack::with_order`].
+pub type MinStack<T> = ExtremumStack<T, fn(&T, &T) -> Ordering>;
+
+impl<T, F: FnMut(&T, &T) -> Ordering> ExtremumStack<T, F> {
+    /// Create an empty stack, which tracks the least value according to `order`, without allocating.
+    #[inline]
+    pub const fn with_order(order: F) -> Self {
+        Self {
+            values: MyVec::new(),
+            extrema: MyVec::new(),
+            order,
+        }
+    }
+
+    /// Number of values on the stack.
+    #[doc(alias = "size")]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check whether the stack has no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Add `value` to the top of the stack.
+    ///
+    /// A value equal to the current extremum counts as a new extremum, so that popping one of several equal extrema
+    /// leaves the others in place.
+    pub fn push(&mut self, value: T) {
+        let Self {
+            values,
+            extrema,
+            order,
+        } = self;
+        let is_extremum = extrema
+            .last()
+            .is_none_or(|&index| order(&value, &values[index]).is_le());
+        if is_extremum {
+            self.extrema.push(self.values.len());
+        }
+        self.values.push(value);
+    }
+
+    /// Remove the value at the top of the stack and return it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.values.pop()?;
+        if self.extrema.last() == Some(&self.values.len()) {
+            self.extrema.pop();
+        }
+        Some(value)
+    }
+
+    /// Get a reference to the value at the top of the stack, or `None` if it is empty.
+    #[doc(alias = "peek")]
+    #[inline]
+    pub fn top(&self) -> Option<&T> {
+        self.values.last()
+    }
+
+    /// Get a reference to the least value on the stack according to its order, or `None` if it is empty.
+    ///
+    /// If several values are tied, this is the one closest to the top.
+    #[doc(alias("min", "max"))]
+    #[inline]
+    pub fn extremum(&self) -> Option<&T> {
+        self.extrema.last().map(|&index| &self.values[index])
+    }
+
+    /// Drop every value on the stack.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.extrema.clear();
+    }
+
+    /// View the values as a slice, from the bottom of the stack to the top.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T: Ord> MinStack<T> {
+    /// Create an empty stack, which tracks its minimum value, without allocating.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_order(T::cmp)
+    }
+
+    /// Get a reference to the minimum value on the stack, or `None` if it is empty.
+    #[inline]
+    pub fn min(&self) -> Option<&T> {
+        self.extremum()
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T: Ord> Default for MinStack<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+impl<T: Clone, F: Clone> Clone for ExtremumStack<T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            extrema: self.extrema.clone(),
+            order: self.order.clone(),
+        }
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way, from the bottom of the stack to the top.
+impl<T: fmt::Debug, F> fmt::Debug for ExtremumStack<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.values.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_after_every_operation() {
+        let mut stack = MinStack::new();
+        assert_eq!(stack.min(), None);
+        // decreasing, then increasing
+        for (value, min) in [(5, 5), (3, 3), (4, 3), (1, 1), (2, 1), (6, 1)] {
+            stack.push(value);
+            assert_eq!((stack.top(), stack.min()), (Some(&value), Some(&min)));
+        }
+        for (popped, min) in [(6, 1), (2, 1), (1, 3), (4, 3), (3, 5)] {
+            assert_eq!(stack.pop(), Some(popped));
+            assert_eq!(stack.min(), Some(&min));
+        }
+        // popping below the old minimum, then pushing a new one
+        stack.push(0);
+        assert_eq!(stack.min(), Some(&0));
+        assert_eq!(
+            (stack.pop(), stack.pop(), stack.pop()),
+            (Some(0), Some(5), None)
+        );
+        assert_eq!(stack.min(), None);
+    }
+
+    #[test]
+    fn duplicate_minimums() {
+        let mut stack = MinStack::default();
+        for value in [2, 1, 3, 1, 1] {
+            stack.push(value);
+        }
+        for min in [1, 1, 1, 1, 2] {
+            assert_eq!(stack.min(), Some(&min));
+            stack.pop();
+        }
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn custom_orders() {
+        let mut max_stack = MinStack::new();
+        let mut by_len = ExtremumStack::with_order(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+        for word in ["tree", "a", "forest", "to", "leaf"] {
+            max_stack.push(core::cmp::Reverse(word.len()));
+            by_len.push(word);
+        }
+        assert_eq!(max_stack.min(), Some(&core::cmp::Reverse(6)));
+        assert_eq!(by_len.extremum(), Some(&"a"));
+        assert_eq!(
+            std::format!("{by_len:?}"),
+            r#"["tree", "a", "forest", "to", "leaf"]"#
+        );
+
+        let mut clone = by_len.clone();
+        clone.pop();
+        clone.pop();
+        clone.pop();
+        assert_eq!(clone.extremum(), Some(&"a"));
+        clone.pop();
+        assert_eq!(
+            (clone.as_slice(), clone.extremum()),
+            (&["tree"][..], Some(&"tree"))
+        );
+        by_len.clear();
+        assert_eq!((by_len.len(), by_len.extremum()), (0, None));
+    }
+}
@@ -4,27 +4,83 @@
 //! and contains no functionality beyond storing a single value (a number, in the tests).
 //! For this implementation, it has been expanded to cover all reasonable `T`.
 
-use core::fmt;
+use core::{
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    future::Future,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
-pub use implementation::MyBox;
+use crate::allocator::Allocator;
+use implementation::PartialSlice;
+pub use implementation::{IntoIter, MyBox};
 
 /// Unsafe-restraining module.
 #[allow(unsafe_code)]
 mod implementation {
+    use crate::allocator::{AllocError, Allocator, Global};
     use core::{
         alloc::Layout,
-        mem::ManuallyDrop,
+        any::Any,
+        convert::Infallible,
+        marker::PhantomData,
+        mem::{self, ManuallyDrop, MaybeUninit},
         ops::{Deref, DerefMut},
-        ptr::NonNull,
+        pin::Pin,
+        ptr::{self, NonNull},
+        str::Utf8Error,
     };
 
     /// Simple heap allocation of a single value.
     ///
     /// Like [`alloc::boxed::Box`], this type stores a single `T` on the heap.
+    /// `T` may also be a slice (`MyBox<[T]>`), which stores its length alongside the pointer.
+    /// The memory comes from the [`Global`] heap by default, or from any other [`Allocator`] with [`new_in`](MyBox::new_in).
+    ///
+    /// `MyBox<T>` is [`Send`] only if `T` is:
+    ///
+    /// ```compile_fail
+    /// # use datastructures_and_algorithms::my_box::MyBox;
+    /// fn assert_send<T: Send>(_: &T) {}
+    /// assert_send(&MyBox::new(std::rc::Rc::new(5)));
+    /// ```
+    ///
+    /// And [`Sync`] only if `T` is:
+    ///
+    /// ```compile_fail
+    /// # use datastructures_and_algorithms::my_box::MyBox;
+    /// fn assert_sync<T: Sync>(_: &T) {}
+    /// assert_sync(&MyBox::new(core::cell::Cell::new(5)));
+    /// ```
+    ///
+    /// Like `Box`, the drop checker knows that a `MyBox<T>` owns its `T`, so the box can't outlive data that `T` borrows:
+    ///
+    /// ```compile_fail
+    /// # use datastructures_and_algorithms::my_box::MyBox;
+    /// struct PrintOnDrop<'a>(&'a str);
+    /// impl Drop for PrintOnDrop<'_> {
+    ///     fn drop(&mut self) {
+    ///         println!("dropping {}", self.0);
+    ///     }
+    /// }
+    ///
+    /// let boxed;
+    /// let referent = String::from("referent");
+    /// boxed = MyBox::new(PrintOnDrop(&referent));
+    /// // `referent` is dropped before `boxed`, so `boxed`'s destructor would read a dangling reference
+    /// ```
     #[doc(alias = "MyNumber")]
-    pub struct MyBox<T> {
-        // critical invariant: `inner` must be a valid pointer to a valid T, and if T is not zero-sized it must be possible to dealloc it
+    pub struct MyBox<T: ?Sized, A: Allocator = Global> {
+        // critical invariant: `inner` must be a valid pointer to a valid T, and if T is not zero-sized it must be possible to deallocate it from `alloc` (with `Layout::for_value`)
         inner: NonNull<T>,
+        // tells the drop checker (and variance) that a `MyBox<T>` owns a `T`, rather than just pointing to one
+        _owns: PhantomData<T>,
+        alloc: A,
     }
 
     impl<T> MyBox<T> {
@@ -33,34 +89,166 @@ mod implementation {
         /// Place the given `value` on the heap.
         #[inline]
         pub fn new(value: T) -> Self {
-            // find the correct size+alignment requirements for this value
-            if Self::INNER_LAYOUT.size() == 0 {
-                // special case: 0-sized types can not be safely allocated!
-                return Self {
-                    inner: NonNull::dangling(),
-                };
-            }
-            // SAFETY: T is not zero-size
-            let raw = unsafe { alloc::alloc::alloc(Self::INNER_LAYOUT) };
-            // convert the pointer into the proper pointer type
-            let inner = match NonNull::new(raw) {
-                Some(u8_inner) => u8_inner.cast::<T>(),
-                None => alloc::alloc::handle_alloc_error(Self::INNER_LAYOUT),
+            Self::new_in(value, Global)
+        }
+
+        /// Place the given `value` on the heap, or give it back (inside the error) if the allocation fails.
+        ///
+        /// Unlike [`new`](Self::new), this never aborts on allocation failure.
+        #[inline]
+        pub fn try_new(value: T) -> Result<Self, AllocError<T>> {
+            Self::try_new_in(value, Global)
+        }
+
+        /// Allocate space for a `T` on the heap, without initializing it.
+        ///
+        /// This allows large values to be written directly into the allocation, instead of being built on the stack and then moved.
+        #[inline]
+        pub fn new_uninit() -> MyBox<MaybeUninit<T>> {
+            Self::new_uninit_in(Global)
+        }
+
+        /// Allocate space for a `T` on the heap, with every byte set to zero.
+        ///
+        /// Zeroed memory is not necessarily a valid `T` (e.g. references and [`NonZero`](core::num::NonZero) can't be zero),
+        /// so it is still the caller's responsibility to ensure that before calling [`assume_init`](MyBox::assume_init).
+        #[inline]
+        pub fn new_zeroed() -> MyBox<MaybeUninit<T>> {
+            Self::new_zeroed_in(Global)
+        }
+
+        /// Allocate space for a `T` on the heap, then place the result of `f` directly into it.
+        ///
+        /// Since the allocation happens first, large values can be built in place rather than on the stack and then moved.
+        /// If `f` panics, the allocation is freed.
+        #[inline]
+        pub fn filled_with(f: impl FnOnce() -> T) -> Self {
+            Self::filled_with_in(f, Global)
+        }
+
+        /// Box the given zero-sized `value` at compile time.
+        ///
+        /// Zero-sized values never need an allocation, so unlike [`new`](Self::new) this is a `const fn`.
+        /// Using it with a type that isn't zero-sized fails to compile:
+        ///
+        /// ```compile_fail
+        /// # use datastructures_and_algorithms::my_box::MyBox;
+        /// const BOXED: MyBox<u8> = MyBox::new_zst(5);
+        /// ```
+        #[inline]
+        pub const fn new_zst(value: T) -> Self {
+            const { assert!(size_of::<T>() == 0, "`new_zst` requires a zero-sized type") };
+            // the value now belongs to the box, which reads and drops it through a dangling pointer (valid for ZSTs)
+            mem::forget(value);
+            Self {
+                inner: NonNull::dangling(),
+                _owns: PhantomData,
+                alloc: Global,
+            }
+        }
+
+        /// Place the given `value` on the heap, and pin it there.
+        #[inline]
+        pub fn pin(value: T) -> Pin<Self> {
+            Self::new(value).into_pin()
+        }
+    }
+
+    impl<T, A: Allocator> MyBox<T, A> {
+        /// Place the given `value` in memory from `alloc`.
+        #[inline]
+        pub fn new_in(value: T, alloc: A) -> Self {
+            match Self::try_new_in(value, alloc) {
+                Ok(this) => this,
+                Err(_) => alloc::alloc::handle_alloc_error(MyBox::<T>::INNER_LAYOUT),
+            }
+        }
+
+        /// Place the given `value` in memory from `alloc`, or give it back (inside the error) if the allocation fails.
+        ///
+        /// Unlike [`new_in`](Self::new_in), this never aborts on allocation failure.
+        pub fn try_new_in(value: T, alloc: A) -> Result<Self, AllocError<T>> {
+            let Some(inner) = Self::try_allocate(&alloc, false) else {
+                // nothing was allocated, so there's nothing to free
+                return Err(AllocError::new(value));
             };
-            // write the value into the new allocation
-            // SAFETY: `inner` has no outstanding references and is of the proper layout
+            // write the value into the new allocation (a no-op for ZSTs, but it keeps `value` from being dropped here)
+            // SAFETY: `inner` has no outstanding references and is of the proper layout (dangling is valid for ZSTs)
             unsafe { inner.write(value) };
 
             // we now have a properly-initialized value!
-            Self { inner }
+            Ok(Self {
+                inner,
+                _owns: PhantomData,
+                alloc,
+            })
+        }
+
+        /// Allocate space for a `T` in memory from `alloc`, then place the result of `f` directly into it.
+        ///
+        /// If `f` panics, the allocation is freed.
+        #[inline]
+        pub fn filled_with_in(f: impl FnOnce() -> T, alloc: A) -> Self {
+            Self::new_uninit_in(alloc).init(f())
+        }
+
+        /// Allocate space for a `T` in memory from `alloc`, without initializing it.
+        #[inline]
+        pub fn new_uninit_in(alloc: A) -> MyBox<MaybeUninit<T>, A> {
+            let inner = Self::try_allocate(&alloc, false)
+                .unwrap_or_else(|| alloc::alloc::handle_alloc_error(MyBox::<T>::INNER_LAYOUT));
+            // `MaybeUninit<T>` has the same layout as `T`, and is always valid, even uninitialized
+            MyBox {
+                inner: inner.cast::<MaybeUninit<T>>(),
+                _owns: PhantomData,
+                alloc,
+            }
+        }
+
+        /// Allocate space for a `T` in memory from `alloc`, with every byte set to zero.
+        ///
+        /// As with [`new_zeroed`](MyBox::new_zeroed), zeroed memory is not necessarily a valid `T`.
+        #[inline]
+        pub fn new_zeroed_in(alloc: A) -> MyBox<MaybeUninit<T>, A> {
+            let inner = Self::try_allocate(&alloc, true)
+                .unwrap_or_else(|| alloc::alloc::handle_alloc_error(MyBox::<T>::INNER_LAYOUT));
+            // `MaybeUninit<T>` has the same layout as `T`, and is always valid, even uninitialized
+            MyBox {
+                inner: inner.cast::<MaybeUninit<T>>(),
+                _owns: PhantomData,
+                alloc,
+            }
+        }
+
+        /// Allocate uninitialized (or `zeroed`) space for a `T` from `alloc`, or `None` if the allocator fails.
+        ///
+        /// Zero-sized types never allocate, and get a dangling (but aligned) pointer instead.
+        fn try_allocate(alloc: &A, zeroed: bool) -> Option<NonNull<T>> {
+            // find the correct size+alignment requirements for this value
+            let layout = MyBox::<T>::INNER_LAYOUT;
+            if layout.size() == 0 {
+                // special case: 0-sized types are never allocated, whatever the allocator
+                return Some(NonNull::dangling());
+            }
+            let raw = if zeroed {
+                alloc.allocate_zeroed(layout)
+            } else {
+                alloc.allocate(layout)
+            };
+            // convert the pointer into the proper pointer type
+            raw.ok().map(NonNull::cast::<T>)
         }
 
         /// Remove the value from the heap and return it, deallocating the box.
         #[inline]
         pub fn into_inner(self) -> T {
+            self.into_inner_with_allocator().0
+        }
+
+        /// Remove the value from the heap, deallocating the box, and return it along with the allocator.
+        fn into_inner_with_allocator(self) -> (T, A) {
             // ensure that `self` is never dropped, and so the original destructor can never accidentally run after this call
-            let manually_drop = ManuallyDrop::new(self);
-            let inner = manually_drop.inner;
+            let (inner, alloc) = self.into_parts();
             // move the value from the heap to the stack
             /*
             SAFETY:
@@ -69,24 +257,601 @@ mod implementation {
             */
             let value: T = unsafe { inner.read() };
 
-            if Self::INNER_LAYOUT.size() == 0 {
-                // special case: can't, and don't need to, free 0-size values
-                return value;
+            if MyBox::<T>::INNER_LAYOUT.size() != 0 {
+                // deallocate the heap pointer (0-size values were never allocated, so they can't be freed)
+                // SAFETY: pointer is unchanged from the pointer returned by `allocate`, and the layout is identical, fulfilling `deallocate`'s requirements
+                unsafe { alloc.deallocate(inner.cast::<u8>(), MyBox::<T>::INNER_LAYOUT) };
             }
 
-            // get a de-allocatable pointer to the heap allocation
-            let raw_inner = inner.as_ptr().cast::<u8>();
-            // deallocate the heap pointer
-            // SAFETY: pointer is unchanged from the pointer returned by `alloc`, and the layout is identical, fulfilling `dealloc`'s requirements
-            unsafe { alloc::alloc::dealloc(raw_inner, Self::INNER_LAYOUT) };
-
             // return the inner value
-            value
+            (value, alloc)
+        }
+
+        /// Transform the value in the box with `f`.
+        ///
+        /// When `U` has the same size and alignment as `T`, the allocation is reused instead of being freed and reallocated.
+        /// If `f` panics, the allocation is freed.
+        pub fn map<U>(self, f: impl FnOnce(T) -> U) -> MyBox<U, A> {
+            let Ok(mapped) = self.try_map(|value| Ok::<U, Infallible>(f(value)));
+            mapped
+        }
+
+        /// Transform the value in the box with `f`, unless it fails.
+        ///
+        /// When `U` has the same size and alignment as `T`, the allocation is reused instead of being freed and reallocated.
+        /// If `f` fails (or panics), the allocation is freed.
+        pub fn try_map<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<MyBox<U, A>, E> {
+            if Layout::new::<U>() != MyBox::<T>::INNER_LAYOUT {
+                let (value, alloc) = self.into_inner_with_allocator();
+                return f(value).map(|mapped| MyBox::new_in(mapped, alloc));
+            }
+            let (value, slot) = self.take_value();
+            // if `f` fails or panics, dropping `slot` frees the allocation
+            let mapped = f(value)?;
+            Ok(slot.cast_uninit::<U>().init(mapped))
+        }
+
+        /// Move the value out of the box, keeping the (now uninitialized) allocation.
+        fn take_value(self) -> (T, MyBox<MaybeUninit<T>, A>) {
+            // the allocation moves to the uninitialized box, so this one must not free it (or drop the value)
+            let (inner, alloc) = self.into_parts();
+            // SAFETY: `inner` is valid as a `T`, and is only ever accessed as a `MaybeUninit<T>` after this
+            let value = unsafe { inner.read() };
+            let slot = MyBox {
+                inner: inner.cast::<MaybeUninit<T>>(),
+                _owns: PhantomData,
+                alloc,
+            };
+            (value, slot)
+        }
+    }
+
+    impl<T: ?Sized> MyBox<T> {
+        /// Consume the box without freeing it, returning a raw pointer to the value.
+        ///
+        /// The value and its allocation are leaked unless ownership is given back with [`from_raw`](Self::from_raw).
+        /// For zero-sized types, this is a dangling (but aligned and non-null) pointer.
+        #[inline]
+        pub fn into_raw(self) -> *mut T {
+            self.into_raw_with_allocator().0
+        }
+
+        /// Take ownership of a value previously given up by [`into_raw`](Self::into_raw) (or [`leak`](Self::leak)).
+        ///
+        /// # Safety
+        ///
+        /// - `raw` must have come from `MyBox::<T>::into_raw` (or `leak`), with the same `T` (and, for slices, the same length).
+        ///   [`Box::into_raw`](alloc::boxed::Box::into_raw) is also allowed, since `Box` allocates the same way.
+        /// - Ownership may only be taken back once per `into_raw` call; the same pointer can't be passed here twice.
+        /// - The value must still be valid, and not be accessed through any other pointer after this call.
+        #[inline]
+        pub unsafe fn from_raw(raw: *mut T) -> Self {
+            // SAFETY: the caller upholds `from_raw_in`'s requirements, and `Global` is the allocator `into_raw` boxes use
+            unsafe { Self::from_raw_in(raw, Global) }
+        }
+
+        /// Adopt the allocation of a standard [`Box`](alloc::boxed::Box), without copying or reallocating.
+        ///
+        /// Since `Box` supports unsized coercions on stable Rust, this is how to make trait objects like `MyBox<dyn Fn()>`.
+        #[inline]
+        pub fn from_box(boxed: alloc::boxed::Box<T>) -> Self {
+            let raw = alloc::boxed::Box::into_raw(boxed);
+            /*
+            SAFETY:
+            - `Box` uses the global allocator with `Layout::for_value`, and never allocates for zero-sized values, exactly like `MyBox`
+            - `raw` is non-null and uniquely owned, since it was just taken from the box
+            */
+            unsafe { Self::from_raw(raw) }
+        }
+
+        /// Hand the allocation over to a standard [`Box`](alloc::boxed::Box), without copying or reallocating.
+        ///
+        /// This is the reverse of `From<Box<T>>`; the orphan rules don't allow `From<MyBox<T>> for Box<T>`, since `Box` is `#[fundamental]`.
+        #[inline]
+        pub fn into_box(self) -> alloc::boxed::Box<T> {
+            let raw = self.into_raw();
+            /*
+            SAFETY:
+            - `MyBox<T>` uses the global allocator with `Layout::for_value`, and never allocates for zero-sized values, exactly like `Box`
+            - `raw` is non-null (dangling and aligned for zero-sized values) and uniquely owned, since it was just taken from the box
+            */
+            unsafe { alloc::boxed::Box::from_raw(raw) }
+        }
+    }
+
+    impl<T: ?Sized, A: Allocator> MyBox<T, A> {
+        /// Get a raw pointer to the value, without giving up ownership of it.
+        ///
+        /// For zero-sized types, this is a dangling (but aligned and non-null) pointer.
+        #[inline]
+        pub fn as_ptr(&self) -> *const T {
+            self.inner.as_ptr()
+        }
+
+        /// Get a raw mutable pointer to the value, without giving up ownership of it.
+        ///
+        /// For zero-sized types, this is a dangling (but aligned and non-null) pointer.
+        #[inline]
+        pub fn as_mut_ptr(&mut self) -> *mut T {
+            self.inner.as_ptr()
+        }
+
+        /// Get a reference to the allocator the value lives in.
+        ///
+        /// This is an associated function (`MyBox::allocator(&b)`), so it doesn't hide a method of the same name on `T`.
+        #[inline]
+        pub fn allocator(this: &Self) -> &A {
+            &this.alloc
+        }
+
+        /// Consume the box without freeing it, returning a raw pointer to the value and the allocator it lives in.
+        ///
+        /// The value and its allocation are leaked unless ownership is given back with [`from_raw_in`](Self::from_raw_in).
+        #[inline]
+        pub fn into_raw_with_allocator(self) -> (*mut T, A) {
+            let (inner, alloc) = self.into_parts();
+            (inner.as_ptr(), alloc)
+        }
+
+        /// Take ownership of a value previously given up by [`into_raw_with_allocator`](Self::into_raw_with_allocator).
+        ///
+        /// # Safety
+        ///
+        /// The same requirements as [`from_raw`](MyBox::from_raw), except that `raw` must have been allocated by `alloc`
+        /// (or the allocator it was cloned from or into).
+        #[inline]
+        pub unsafe fn from_raw_in(raw: *mut T, alloc: A) -> Self {
+            Self {
+                // SAFETY: `into_raw_with_allocator` never returns a null pointer (even for ZSTs, where it is dangling but non-null)
+                inner: unsafe { NonNull::new_unchecked(raw) },
+                _owns: PhantomData,
+                alloc,
+            }
+        }
+
+        /// Consume the box without running its destructor, splitting it into its pointer and allocator.
+        fn into_parts(self) -> (NonNull<T>, A) {
+            // ensure that `self` is never dropped, since ownership moves to the caller
+            let this = ManuallyDrop::new(self);
+            // SAFETY: `this` is never used (or dropped) again, so this is a move out of it
+            let alloc = unsafe { ptr::read(&this.alloc) };
+            (this.inner, alloc)
+        }
+
+        /// Consume the box without freeing it, returning a reference to the value that lives for as long as needed.
+        ///
+        /// The value is never dropped, and its memory is never reclaimed.
+        #[inline]
+        pub fn leak<'a>(self) -> &'a mut T
+        where
+            A: 'a,
+        {
+            // ensure that `self` is never dropped, so the value and its allocation stay alive forever
+            let mut manually_drop = ManuallyDrop::new(self);
+            // SAFETY: `inner` is valid as a `T`, and nothing else can ever access it again since the box is gone
+            unsafe { manually_drop.inner.as_mut() }
+        }
+
+        /// Pin the value in place on the heap, so it can never be moved again.
+        ///
+        /// Unlike `Box::into_pin`, this is a method, to match [`into_inner`](Self::into_inner).
+        #[inline]
+        pub fn into_pin(self) -> Pin<Self>
+        where
+            A: 'static,
+        {
+            /*
+            SAFETY:
+            - the value lives in its own heap allocation, which does not move when the box itself is moved
+            - `Pin` only allows moving the value back out (`Pin::into_inner`) when `T: Unpin`
+            - `Drop` runs the value's destructor in place, before the allocation is freed
+            - the allocator is `'static`, so its memory can't be reused by forgetting the box and then dropping the allocator
+            */
+            unsafe { Pin::new_unchecked(self) }
+        }
+    }
+
+    impl<T, A: Allocator> MyBox<MaybeUninit<T>, A> {
+        /// Convert to a box of the initialized value, reusing the same allocation.
+        ///
+        /// # Safety
+        ///
+        /// The contents must have been fully initialized as a valid `T` (see [`MaybeUninit::assume_init`]).
+        #[inline]
+        pub unsafe fn assume_init(self) -> MyBox<T, A> {
+            // the allocation now belongs to the new box, so this one must not free it
+            let (inner, alloc) = self.into_parts();
+            // `MaybeUninit<T>` has the same layout as `T`, and the caller guarantees the value is initialized
+            MyBox {
+                inner: inner.cast::<T>(),
+                _owns: PhantomData,
+                alloc,
+            }
+        }
+
+        /// Write `value` into the allocation, converting to a box of the initialized value.
+        fn init(self, value: T) -> MyBox<T, A> {
+            // the allocation now belongs to the new box, so this one must not free it
+            let (inner, alloc) = self.into_parts();
+            let inner = inner.cast::<T>();
+            // SAFETY: `inner` is valid for writes of a `T` (dangling is valid for ZSTs), and nothing else can access it
+            unsafe { inner.write(value) };
+            MyBox {
+                inner,
+                _owns: PhantomData,
+                alloc,
+            }
+        }
+
+        /// Reuse the allocation for a value of type `U`.
+        ///
+        /// # Panics
+        ///
+        /// If `U` doesn't have exactly the same layout as `T`.
+        fn cast_uninit<U>(self) -> MyBox<MaybeUninit<U>, A> {
+            assert_eq!(
+                Layout::new::<U>(),
+                Layout::new::<T>(),
+                "layouts must match to reuse an allocation"
+            );
+            // the allocation now belongs to the new box, so this one must not free it
+            let (inner, alloc) = self.into_parts();
+            // the layouts match, and `MaybeUninit<U>` is always valid, even uninitialized
+            MyBox {
+                inner: inner.cast::<MaybeUninit<U>>(),
+                _owns: PhantomData,
+                alloc,
+            }
+        }
+    }
+
+    impl<T> MyBox<[T]> {
+        /// Layout of a slice of `len` values of `T`.
+        ///
+        /// # Panics
+        ///
+        /// If the slice would be larger than `isize::MAX` bytes.
+        fn array_layout(len: usize) -> Layout {
+            Layout::array::<T>(len).expect("capacity overflow")
+        }
+
+        /// Allocate space for `len` values of `T` on the heap, without initializing them.
+        ///
+        /// Empty slices (and slices of zero-sized types) never allocate.
+        pub fn new_uninit_slice(len: usize) -> MyBox<[MaybeUninit<T>]> {
+            let layout = Self::array_layout(len);
+            let data = if layout.size() == 0 {
+                // special case: 0-sized slices can not be safely allocated!
+                NonNull::dangling()
+            } else {
+                // SAFETY: the slice is not zero-size
+                let raw = unsafe { alloc::alloc::alloc(layout) };
+                match NonNull::new(raw) {
+                    Some(u8_data) => u8_data.cast::<MaybeUninit<T>>(),
+                    None => alloc::alloc::handle_alloc_error(layout),
+                }
+            };
+            // `MaybeUninit<T>` is always valid, even uninitialized
+            MyBox {
+                inner: NonNull::slice_from_raw_parts(data, len),
+                _owns: PhantomData,
+                alloc: Global,
+            }
+        }
+
+        /// Build a slice of `len` values on the heap, where the value at each index `i` is `f(i)`.
+        ///
+        /// If `f` panics, the values it already returned are dropped, and the allocation is freed.
+        pub fn from_fn(len: usize, mut f: impl FnMut(usize) -> T) -> Self {
+            let mut partial = PartialSlice::with_len(len);
+            for i in 0..len {
+                partial.push(f(i));
+            }
+            partial.finish()
+        }
+    }
+
+    impl<T> MyBox<[MaybeUninit<T>]> {
+        /// Convert to a box of the initialized slice, reusing the same allocation.
+        ///
+        /// # Safety
+        ///
+        /// Every element must have been fully initialized as a valid `T` (see [`MaybeUninit::assume_init`]).
+        #[inline]
+        pub unsafe fn assume_init(self) -> MyBox<[T]> {
+            // the allocation now belongs to the new box, so this one must not free it
+            let this = ManuallyDrop::new(self);
+            // `MaybeUninit<T>` has the same layout as `T`, and the caller guarantees every element is initialized
+            MyBox {
+                inner: NonNull::slice_from_raw_parts(this.inner.cast::<T>(), this.inner.len()),
+                _owns: PhantomData,
+                alloc: Global,
+            }
+        }
+
+        /// Change the length of the slice in place, keeping the existing elements (up to the new length).
+        ///
+        /// # Panics
+        ///
+        /// If the new slice would be larger than `isize::MAX` bytes, in which case nothing is changed.
+        fn resize(&mut self, new_len: usize) {
+            let old_len = self.inner.len();
+            if new_len == old_len {
+                return;
+            }
+            // find both layouts before touching anything, so a panic leaves `self` unchanged
+            let old_layout = MyBox::<[T]>::array_layout(old_len);
+            let new_layout = MyBox::<[T]>::array_layout(new_len);
+            let old_data = self.inner.cast::<u8>();
+
+            let new_data = match (old_layout.size(), new_layout.size()) {
+                (0, 0) => old_data,
+                (_, 0) => {
+                    // SAFETY: the old allocation is not zero-size, and was allocated with `old_layout`
+                    unsafe { alloc::alloc::dealloc(old_data.as_ptr(), old_layout) };
+                    NonNull::<MaybeUninit<T>>::dangling().cast::<u8>()
+                }
+                (0, _) => {
+                    // SAFETY: the new slice is not zero-size
+                    let raw = unsafe { alloc::alloc::alloc(new_layout) };
+                    NonNull::new(raw)
+                        .unwrap_or_else(|| alloc::alloc::handle_alloc_error(new_layout))
+                }
+                (_, new_size) => {
+                    // SAFETY: the old allocation is not zero-size and was allocated with `old_layout`, and `new_size` is non-zero and fits in `isize` (checked by `array_layout`)
+                    let raw =
+                        unsafe { alloc::alloc::realloc(old_data.as_ptr(), old_layout, new_size) };
+                    // on failure, the old allocation is untouched and still owned by `self`
+                    NonNull::new(raw)
+                        .unwrap_or_else(|| alloc::alloc::handle_alloc_error(new_layout))
+                }
+            };
+            self.inner = NonNull::slice_from_raw_parts(new_data.cast::<MaybeUninit<T>>(), new_len);
+        }
+    }
+
+    /// A heap slice being filled in from the front, which can grow as needed.
+    ///
+    /// If it is dropped before [`finish`](Self::finish) (e.g. due to a panic), it drops exactly the values that were pushed.
+    pub(super) struct PartialSlice<T> {
+        // invariant: the first `initialized` elements of `slots` are initialized
+        slots: MyBox<[MaybeUninit<T>]>,
+        initialized: usize,
+    }
+
+    impl<T> PartialSlice<T> {
+        /// Start with room for `len` values.
+        pub(super) fn with_len(len: usize) -> Self {
+            Self {
+                slots: MyBox::<[T]>::new_uninit_slice(len),
+                initialized: 0,
+            }
+        }
+
+        /// Add `value` after the previously-pushed values, growing the slice if it is full.
+        pub(super) fn push(&mut self, value: T) {
+            if self.initialized == self.slots.len() {
+                // double the size, to keep the total cost of growing linear
+                let new_len = self.slots.len().saturating_mul(2).max(4);
+                assert!(new_len > self.initialized, "capacity overflow");
+                self.slots.resize(new_len);
+            }
+            self.slots[self.initialized].write(value);
+            self.initialized += 1;
+        }
+
+        /// Trim the slice down to the pushed values, and convert it to an initialized box.
+        pub(super) fn finish(mut self) -> MyBox<[T]> {
+            self.slots.resize(self.initialized);
+            // `self` must not drop the values that now belong to the result
+            let this = ManuallyDrop::new(self);
+            // SAFETY: `this` is never used (or dropped) again, so this is a move out of it
+            let slots = unsafe { ptr::read(&this.slots) };
+            // SAFETY: every slot up to `initialized` (which is now all of them) has been written
+            unsafe { slots.assume_init() }
+        }
+    }
+
+    impl<T> Drop for PartialSlice<T> {
+        fn drop(&mut self) {
+            let initialized = ptr::slice_from_raw_parts_mut(
+                self.slots.as_mut_ptr().cast::<T>(),
+                self.initialized,
+            );
+            // SAFETY: the first `initialized` slots are initialized, and are never accessed again (the slots themselves are freed right after this)
+            unsafe { initialized.drop_in_place() };
+        }
+    }
+
+    impl<T> MyBox<[T]> {
+        /// View the slice as uninitialized slots, reusing the same allocation.
+        ///
+        /// The values are not dropped by the resulting box; the caller becomes responsible for them.
+        fn into_uninit_slice(self) -> MyBox<[MaybeUninit<T>]> {
+            // `[MaybeUninit<T>]` has the same layout (and pointer metadata) as `[T]`
+            let raw = self.into_raw() as *mut [MaybeUninit<T>];
+            // SAFETY: `raw` came from `into_raw` with the same layout, and initialized values are valid `MaybeUninit`s
+            unsafe { MyBox::from_raw(raw) }
+        }
+    }
+
+    /// Owning iterator over the values of a boxed slice, created by [`MyBox::into_iter`](IntoIterator::into_iter).
+    ///
+    /// The allocation is freed (along with any values that weren't yielded) when the iterator is dropped.
+    pub struct IntoIter<T> {
+        // invariant: exactly the slots in `start..end` are initialized, and `start <= end <= slots.len()`
+        slots: MyBox<[MaybeUninit<T>]>,
+        start: usize,
+        end: usize,
+    }
+
+    impl<T> IntoIter<T> {
+        /// View the values that haven't been yielded yet.
+        pub fn as_slice(&self) -> &[T] {
+            let remaining = &self.slots[self.start..self.end];
+            // SAFETY: the slots in `start..end` are initialized, and `MaybeUninit<T>` has the same layout as `T`
+            unsafe { &*(ptr::from_ref(remaining) as *const [T]) }
+        }
+
+        /// Mutably view the values that haven't been yielded yet.
+        pub fn as_mut_slice(&mut self) -> &mut [T] {
+            let remaining = &mut self.slots[self.start..self.end];
+            // SAFETY: the slots in `start..end` are initialized, and `MaybeUninit<T>` has the same layout as `T`
+            unsafe { &mut *(ptr::from_mut(remaining) as *mut [T]) }
+        }
+    }
+
+    /// Rust-specific helper to move the values out of a boxed slice.
+    impl<T> IntoIterator for MyBox<[T]> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+
+        fn into_iter(self) -> IntoIter<T> {
+            let slots = self.into_uninit_slice();
+            IntoIter {
+                start: 0,
+                end: slots.len(),
+                slots,
+            }
+        }
+    }
+
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            // SAFETY: the slot at `start` is initialized, and is never read again once `start` moves past it
+            let value = unsafe { self.slots[self.start].assume_init_read() };
+            self.start += 1;
+            Some(value)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.end - self.start;
+            (len, Some(len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for IntoIter<T> {
+        fn next_back(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            self.end -= 1;
+            // SAFETY: the slot at the old `end - 1` is initialized, and is never read again now that `end` has moved before it
+            Some(unsafe { self.slots[self.end].assume_init_read() })
+        }
+    }
+
+    /// Translation of destructor.
+    impl<T> Drop for IntoIter<T> {
+        fn drop(&mut self) {
+            let remaining = ptr::from_mut(self.as_mut_slice());
+            // SAFETY: the remaining values are initialized, and are never accessed again (the slots themselves are freed right after this)
+            unsafe { remaining.drop_in_place() };
         }
     }
 
+    impl MyBox<str> {
+        /// Convert heap bytes into a heap string, reusing the allocation, as long as the bytes are valid UTF-8.
+        pub fn from_utf8(bytes: MyBox<[u8]>) -> Result<Self, Utf8Error> {
+            core::str::from_utf8(&bytes)?;
+            // SAFETY: the bytes were just checked to be valid UTF-8
+            Ok(unsafe { Self::from_utf8_unchecked(bytes) })
+        }
+
+        /// Convert heap bytes into a heap string, reusing the allocation, without checking that they are valid UTF-8.
+        ///
+        /// # Safety
+        ///
+        /// The bytes must be valid UTF-8 (see [`core::str::from_utf8_unchecked`]).
+        #[inline]
+        pub unsafe fn from_utf8_unchecked(bytes: MyBox<[u8]>) -> Self {
+            // `str` has the same layout (and pointer metadata) as `[u8]`
+            let raw = bytes.into_raw() as *mut str;
+            // SAFETY: `raw` came from `into_raw` with the same layout, and the caller guarantees the bytes are valid UTF-8
+            unsafe { Self::from_raw(raw) }
+        }
+
+        /// Convert into the string's bytes, reusing the allocation.
+        #[inline]
+        pub fn into_boxed_bytes(self) -> MyBox<[u8]> {
+            // `[u8]` has the same layout (and pointer metadata) as `str`
+            let raw = self.into_raw() as *mut [u8];
+            // SAFETY: `raw` came from `into_raw` with the same layout, and any bytes are a valid `[u8]`
+            unsafe { MyBox::from_raw(raw) }
+        }
+    }
+
+    /// Rust-specific helper to add downcasting to boxed [`Any`] trait objects.
+    macro_rules! impl_downcast {
+        ($($dyn_any:ty),* $(,)?) => {
+            $(
+                impl<A: Allocator> MyBox<$dyn_any, A> {
+                    /// Convert to a box of the concrete type `T`, reusing the allocation, or give the box back if it holds a different type.
+                    pub fn downcast<T: Any>(self) -> Result<MyBox<T, A>, Self> {
+                        if !<dyn Any>::is::<T>(&*self) {
+                            return Err(self);
+                        }
+                        // drop the vtable, keeping the data pointer
+                        let (raw, alloc) = self.into_raw_with_allocator();
+                        // SAFETY: the value is a `T`, so the allocation (from `alloc`) was made for `T`'s layout
+                        Ok(unsafe { MyBox::from_raw_in(raw.cast::<T>(), alloc) })
+                    }
+
+                    /// Get a reference to the contents, if they are a `T`.
+                    #[inline]
+                    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+                        <dyn Any>::downcast_ref(&**self)
+                    }
+
+                    /// Get a mutable reference to the contents, if they are a `T`.
+                    #[inline]
+                    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+                        <dyn Any>::downcast_mut(&mut **self)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_downcast!(dyn Any, dyn Any + Send, dyn Any + Send + Sync);
+
+    /// Rust-specific helper to copy a string onto the heap.
+    impl From<&str> for MyBox<str> {
+        fn from(string: &str) -> Self {
+            let bytes = MyBox::from_slice(string.as_bytes());
+            // SAFETY: the bytes were copied from a `str`, so they are valid UTF-8
+            unsafe { Self::from_utf8_unchecked(bytes) }
+        }
+    }
+
+    /// Rust-specific helper to allow unsized coercions, like `MyBox<[T; N]>` to `MyBox<[T]>`, or `MyBox<T>` to `MyBox<dyn Trait>`.
+    #[cfg(feature = "nightly")]
+    impl<T: ?Sized + core::marker::Unsize<U>, U: ?Sized, A: Allocator>
+        core::ops::CoerceUnsized<MyBox<U, A>> for MyBox<T, A>
+    {
+    }
+
+    /// Rust-specific helper to allow `self: MyBox<Self>` methods to be called on trait objects.
+    #[cfg(feature = "nightly")]
+    impl<T: ?Sized + core::marker::Unsize<U>, U: ?Sized> core::ops::DispatchFromDyn<MyBox<U>>
+        for MyBox<T>
+    {
+    }
+
+    // SAFETY: `MyBox` uniquely owns its heap `T` (and its allocator), so sending the box to another thread is equivalent to sending the `T` (and `A`) itself
+    unsafe impl<T: ?Sized + Send, A: Allocator + Send> Send for MyBox<T, A> {}
+
+    // SAFETY: `&MyBox<T>` only grants access to a `&T` (via `Deref`) and a `&A`, so sharing the box is equivalent to sharing the `T` (and `A`) itself
+    unsafe impl<T: ?Sized + Sync, A: Allocator + Sync> Sync for MyBox<T, A> {}
+
     /// Translation of requirement to be able to read the value.
-    impl<T> Deref for MyBox<T> {
+    impl<T: ?Sized, A: Allocator> Deref for MyBox<T, A> {
         type Target = T;
 
         #[doc(alias = "read")]
@@ -98,7 +863,7 @@ mod implementation {
     }
 
     /// Translation of requirement to be able to overwrite the value.
-    impl<T> DerefMut for MyBox<T> {
+    impl<T: ?Sized, A: Allocator> DerefMut for MyBox<T, A> {
         #[doc(alias = "write")]
         #[inline]
         fn deref_mut(&mut self) -> &mut T {
@@ -108,75 +873,1878 @@ mod implementation {
     }
 
     /// Translation of destructor.
-    impl<T> Drop for MyBox<T> {
+    impl<T: ?Sized, A: Allocator> Drop for MyBox<T, A> {
         #[inline]
         fn drop(&mut self) {
+            // find the layout while the value is still valid (for slices, this depends on the length)
+            // SAFETY: `inner` is always valid as a reference to a T
+            let layout = Layout::for_value(unsafe { self.inner.as_ref() });
+
             // allow the inner resource to free its own resources, if it has any
             // SAFETY: `inner` is valid until this line, and `drop` is the last function to ever be called on `self` (including not calling `drop` again)
             unsafe { self.inner.drop_in_place() };
 
-            if Self::INNER_LAYOUT.size() == 0 {
+            if layout.size() == 0 {
                 // special case: ZSTs do not allocate, and can not be deallocated
                 return;
             }
 
             // free the internal allocation
-            let raw_inner = self.inner.as_ptr().cast::<u8>();
-            // SAFETY: `inner` was valid as an allocation until this line, the layout matches, and T is not a ZST
-            unsafe { alloc::alloc::dealloc(raw_inner, Self::INNER_LAYOUT) };
+            // SAFETY: `inner` was allocated by `alloc` until this line, the layout matches, and T is not a ZST
+            unsafe { self.alloc.deallocate(self.inner.cast::<u8>(), layout) };
         }
     }
 }
 
-/// Rust-specific helper to visualize this type in a programmer-friendly way.
-impl<T: fmt::Debug> fmt::Debug for MyBox<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("MyBox").field(self as &T).finish()
+impl<T, A: Allocator> MyBox<T, A> {
+    /// Overwrite the value in the box, returning the old one.
+    ///
+    /// The existing allocation is reused, so this never allocates or frees memory.
+    #[inline]
+    pub fn replace(&mut self, value: T) -> T {
+        core::mem::replace(&mut **self, value)
     }
-}
 
-/// Translation of the copy constructor into Rust.
-impl<T: Clone> Clone for MyBox<T> {
-    fn clone(&self) -> Self {
-        Self::new(T::clone(self))
+    /// Take the value out of the box, leaving [`T::default()`](Default::default) in its place.
+    ///
+    /// The existing allocation is reused, so this never allocates or frees memory.
+    #[inline]
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        core::mem::take(&mut **self)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<T: ?Sized, A: Allocator> MyBox<T, A> {
+    /// Swap the contents of two boxes in O(1), by exchanging their heap pointers rather than moving any values.
+    ///
+    /// This is an associated function (`MyBox::swap(&mut a, &mut b)`), so it doesn't hide methods like [`slice::swap`].
+    #[inline]
+    pub fn swap(this: &mut Self, other: &mut Self) {
+        core::mem::swap(this, other);
+    }
 
-    #[test]
-    fn store_numbers() {
-        for i in [1, 2, 3] {
-            let boxed: MyBox<i32> = MyBox::new(i);
-            std::println!("my box stores {n}", n = *boxed);
-        }
+    /// Get a reference to the value, for generic code where deref coercion doesn't kick in.
+    ///
+    /// This is an associated function (`MyBox::get_ref(&b)`), so it doesn't hide methods like [`slice::get`].
+    #[doc(alias = "read")]
+    #[inline]
+    pub fn get_ref(this: &Self) -> &T {
+        this
     }
 
-    #[test]
-    fn number_into_number() {
-        for i in [1, 2, 3] {
-            let boxed: MyBox<i32> = MyBox::new(i);
-            std::println!("my box unwraps into {n}", n = boxed.into_inner());
-            // boxed should not be double-freed
-        }
+    /// Get a mutable reference to the value, for generic code where deref coercion doesn't kick in.
+    ///
+    /// This is an associated function (`MyBox::get_mut(&mut b)`), so it doesn't hide methods like [`slice::get_mut`].
+    #[doc(alias = "write")]
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> &mut T {
+        this
     }
+}
 
-    #[test]
-    fn store_unit_into_inner() {
-        // make sure we can handle zero-sized types correctly
-        let boxed: MyBox<()> = MyBox::new(());
-        std::println!("the box contains {unit:?}", unit = *boxed);
-        std::println!("the box unwraps into {unit:?}", unit = boxed.into_inner());
+/// Rust-specific helper to visualize this type in a programmer-friendly way.
+impl<T: ?Sized + fmt::Debug, A: Allocator> fmt::Debug for MyBox<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MyBox").field(&&**self).finish()
     }
+}
 
-    #[test]
-    fn store_unit_drop() {
+/// Rust-specific helper to print the address of the heap allocation (with `{:p}`).
+impl<T: ?Sized, A: Allocator> fmt::Pointer for MyBox<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.as_ptr(), f)
+    }
+}
+
+/// Rust-specific helper to forward formatting traits to the contents of the box.
+macro_rules! forward_fmt {
+    ($($trait:ident),* $(,)?) => {
+        $(
+            impl<T: ?Sized + fmt::$trait, A: Allocator> fmt::$trait for MyBox<T, A> {
+                #[inline]
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    <T as fmt::$trait>::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+forward_fmt!(
+    Display, LowerHex, UpperHex, Octal, Binary, LowerExp, UpperExp
+);
+
+/// Rust-specific helper to allow boxed errors (including `MyBox<dyn Error>`) to be used as errors themselves.
+impl<E: ?Sized + Error, A: Allocator> Error for MyBox<E, A> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        E::source(self)
+    }
+
+    #[allow(
+        deprecated,
+        reason = "forwarded so the box is indistinguishable from its contents"
+    )]
+    fn description(&self) -> &str {
+        E::description(self)
+    }
+
+    #[allow(
+        deprecated,
+        reason = "forwarded so the box is indistinguishable from its contents"
+    )]
+    fn cause(&self) -> Option<&dyn Error> {
+        E::cause(self)
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+impl<T: Clone, A: Allocator + Clone> Clone for MyBox<T, A> {
+    fn clone(&self) -> Self {
+        Self::new_in(T::clone(self), MyBox::allocator(self).clone())
+    }
+
+    /// Clone `source`'s contents into the existing allocation, instead of allocating a new one.
+    fn clone_from(&mut self, source: &Self) {
+        T::clone_from(self, source);
+    }
+}
+
+/// Translation of the copy constructor into Rust, for slices.
+impl<T: Clone> Clone for MyBox<[T]> {
+    fn clone(&self) -> Self {
+        Self::from_slice(self)
+    }
+
+    /// Clone `source`'s contents into the existing allocation, if it is the right length.
+    fn clone_from(&mut self, source: &Self) {
+        if self.len() == source.len() {
+            self.clone_from_slice(source);
+        } else {
+            *self = source.clone();
+        }
+    }
+}
+
+impl<T: Clone> MyBox<[T]> {
+    /// Copy the given `slice` onto the heap, cloning each element.
+    pub fn from_slice(slice: &[T]) -> Self {
+        Self::from_fn(slice.len(), |i| slice[i].clone())
+    }
+}
+
+/// Rust-specific helper to allow collecting an iterator into a heap slice.
+impl<T> FromIterator<T> for MyBox<[T]> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        // start with the least the iterator promises to yield (growing if it yields more)
+        let mut partial = PartialSlice::with_len(iter.size_hint().0);
+        for value in iter {
+            partial.push(value);
+        }
+        partial.finish()
+    }
+}
+
+/// Translation of the copy constructor into Rust, for strings.
+impl Clone for MyBox<str> {
+    fn clone(&self) -> Self {
+        Self::from(&**self)
+    }
+}
+
+/// Rust-specific helper to view a heap string as its bytes.
+impl From<MyBox<str>> for MyBox<[u8]> {
+    #[inline]
+    fn from(string: MyBox<str>) -> Self {
+        string.into_boxed_bytes()
+    }
+}
+
+/// Rust-specific helper to compare boxes by their contents, rather than by their addresses.
+impl<T: ?Sized + PartialEq, A: Allocator> PartialEq for MyBox<T, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        T::eq(self, other)
+    }
+}
+
+impl<T: ?Sized + Eq, A: Allocator> Eq for MyBox<T, A> {}
+
+/// Rust-specific helper to compare a box's contents directly against an unboxed value.
+impl<T: ?Sized + PartialEq, A: Allocator> PartialEq<T> for MyBox<T, A> {
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        T::eq(self, other)
+    }
+}
+
+/// Rust-specific helper to order boxes by their contents, rather than by their addresses.
+impl<T: ?Sized + PartialOrd, A: Allocator> PartialOrd for MyBox<T, A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        T::partial_cmp(self, other)
+    }
+}
+
+/// Rust-specific helper to order a box's contents directly against an unboxed value.
+impl<T: ?Sized + PartialOrd, A: Allocator> PartialOrd<T> for MyBox<T, A> {
+    #[inline]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        T::partial_cmp(self, other)
+    }
+}
+
+/// Rust-specific helper to compare a boxed string directly against a string slice.
+impl PartialEq<str> for MyBox<alloc::string::String> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        **self == *other
+    }
+}
+
+/// Rust-specific helper to compare a boxed string directly against a string slice.
+impl PartialEq<&str> for MyBox<alloc::string::String> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + Ord, A: Allocator> Ord for MyBox<T, A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        T::cmp(self, other)
+    }
+}
+
+/// Rust-specific helper to hash boxes by their contents, consistent with [`PartialEq`].
+impl<T: ?Sized + Hash, A: Allocator> Hash for MyBox<T, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        T::hash(self, state);
+    }
+}
+
+/// Rust-specific helper to pass a box to generic code expecting a reference.
+impl<T: ?Sized, A: Allocator> AsRef<T> for MyBox<T, A> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+/// Rust-specific helper to pass a box to generic code expecting a mutable reference.
+impl<T: ?Sized, A: Allocator> AsMut<T> for MyBox<T, A> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+/// Rust-specific helper to look up boxed keys by their contents, consistent with the comparison and hashing traits.
+impl<T: ?Sized, A: Allocator> Borrow<T> for MyBox<T, A> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+/// Rust-specific helper to mutably borrow the contents of a box in generic code.
+impl<T: ?Sized, A: Allocator> BorrowMut<T> for MyBox<T, A> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+/// Rust-specific helper to allow moving boxes even when their contents are pinned.
+///
+/// Moving a `MyBox` never moves its heap-allocated contents, so this is sound for any `T`.
+impl<T: ?Sized, A: Allocator> Unpin for MyBox<T, A> {}
+
+/// Rust-specific helper to allow boxed iterators to be used directly as iterators.
+///
+/// Unlike `Box`, this only covers sized iterators (and the `dyn Iterator` objects below), since an impl for every
+/// unsized `I` would prevent boxed slices from being [`IntoIterator`].
+impl<I: Iterator, A: Allocator> Iterator for MyBox<I, A> {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        I::next(self)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        I::size_hint(self)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<I::Item> {
+        I::nth(self, n)
+    }
+
+    /// Unbox the iterator and fold it directly, so that its own (possibly specialized) `fold` is used.
+    #[inline]
+    fn fold<B, F: FnMut(B, I::Item) -> B>(self, init: B, f: F) -> B {
+        self.into_inner().fold(init, f)
+    }
+}
+
+/// Rust-specific helper to allow boxed iterator trait objects to be used directly as iterators.
+macro_rules! forward_dyn_iterator {
+    ($($dyn_iterator:ty),* $(,)?) => {
+        $(
+            impl<'a, T, A: Allocator> Iterator for MyBox<$dyn_iterator, A> {
+                type Item = T;
+
+                #[inline]
+                fn next(&mut self) -> Option<T> {
+                    (**self).next()
+                }
+
+                #[inline]
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (**self).size_hint()
+                }
+
+                #[inline]
+                fn nth(&mut self, n: usize) -> Option<T> {
+                    (**self).nth(n)
+                }
+            }
+        )*
+    };
+}
+
+forward_dyn_iterator!(
+    dyn Iterator<Item = T> + 'a,
+    dyn Iterator<Item = T> + Send + 'a,
+    dyn Iterator<Item = T> + Send + Sync + 'a,
+);
+
+impl<I: DoubleEndedIterator, A: Allocator> DoubleEndedIterator for MyBox<I, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        I::next_back(self)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<I::Item> {
+        I::nth_back(self, n)
+    }
+}
+
+impl<I: ExactSizeIterator, A: Allocator> ExactSizeIterator for MyBox<I, A> {
+    #[inline]
+    fn len(&self) -> usize {
+        I::len(self)
+    }
+}
+
+impl<I: FusedIterator, A: Allocator> FusedIterator for MyBox<I, A> {}
+
+/// Rust-specific helper to allow calling a boxed closure directly, as in `boxed(21)`.
+///
+/// Calling consumes the box, moving the closure out of it.
+/// On stable Rust (without the `nightly` feature), call through the box instead, as in `(*boxed)(21)`.
+#[cfg(feature = "nightly")]
+impl<Args: core::marker::Tuple, F: FnOnce<Args>, A: Allocator> FnOnce<Args> for MyBox<F, A> {
+    type Output = F::Output;
+
+    extern "rust-call" fn call_once(self, args: Args) -> F::Output {
+        self.into_inner().call_once(args)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<Args: core::marker::Tuple, F: FnMut<Args>, A: Allocator> FnMut<Args> for MyBox<F, A> {
+    extern "rust-call" fn call_mut(&mut self, args: Args) -> F::Output {
+        F::call_mut(self, args)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<Args: core::marker::Tuple, F: Fn<Args>, A: Allocator> Fn<Args> for MyBox<F, A> {
+    extern "rust-call" fn call(&self, args: Args) -> F::Output {
+        F::call(self, args)
+    }
+}
+
+/// Rust-specific helper to allow calling boxed closure trait objects directly, as in `boxed(21)`.
+///
+/// `dyn FnOnce` can't be moved out of the box, so it can only be called after converting it with [`MyBox::into_box`].
+#[cfg(feature = "nightly")]
+macro_rules! forward_dyn_fn {
+    (FnMut: $($dyn_fn_mut:ty),*; Fn: $($dyn_fn:ty),* $(,)?) => {
+        $(
+            impl<'a, Args: core::marker::Tuple, R, A: Allocator> FnOnce<Args> for MyBox<$dyn_fn_mut, A> {
+                type Output = R;
+
+                extern "rust-call" fn call_once(mut self, args: Args) -> R {
+                    (*self).call_mut(args)
+                }
+            }
+
+            impl<'a, Args: core::marker::Tuple, R, A: Allocator> FnMut<Args> for MyBox<$dyn_fn_mut, A> {
+                extern "rust-call" fn call_mut(&mut self, args: Args) -> R {
+                    (**self).call_mut(args)
+                }
+            }
+        )*
+        $(
+            impl<'a, Args: core::marker::Tuple, R, A: Allocator> FnOnce<Args> for MyBox<$dyn_fn, A> {
+                type Output = R;
+
+                extern "rust-call" fn call_once(self, args: Args) -> R {
+                    (*self).call(args)
+                }
+            }
+
+            impl<'a, Args: core::marker::Tuple, R, A: Allocator> FnMut<Args> for MyBox<$dyn_fn, A> {
+                extern "rust-call" fn call_mut(&mut self, args: Args) -> R {
+                    (**self).call(args)
+                }
+            }
+
+            impl<'a, Args: core::marker::Tuple, R, A: Allocator> Fn<Args> for MyBox<$dyn_fn, A> {
+                extern "rust-call" fn call(&self, args: Args) -> R {
+                    (**self).call(args)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "nightly")]
+forward_dyn_fn!(
+    FnMut: dyn FnMut<Args, Output = R> + 'a, dyn FnMut<Args, Output = R> + Send + 'a;
+    Fn: dyn Fn<Args, Output = R> + 'a, dyn Fn<Args, Output = R> + Send + Sync + 'a,
+);
+
+/// Rust-specific helper to allow boxed futures to be awaited directly.
+///
+/// Futures that aren't [`Unpin`] (like `async` blocks) must be pinned first, with [`MyBox::pin`];
+/// `Pin<MyBox<F>>` is then a future through the standard library's `impl Future for Pin<P>`.
+impl<F: ?Sized + Future + Unpin, A: Allocator> Future for MyBox<F, A> {
+    type Output = F::Output;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // `F: Unpin`, so it can be re-pinned in place without any projection
+        F::poll(Pin::new(&mut **self), cx)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+/// Rust-specific helper to iterate over a boxed slice by reference, as in `for value in &boxed`.
+impl<'a, T, A: Allocator> IntoIterator for &'a MyBox<[T], A> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Rust-specific helper to iterate over a boxed slice by mutable reference, as in `for value in &mut boxed`.
+impl<'a, T, A: Allocator> IntoIterator for &'a mut MyBox<[T], A> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T: Default> Default for MyBox<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Rust-specific helper to allow boxing via [`Into`].
+impl<T> From<T> for MyBox<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Rust-specific helper to adopt a standard [`Box`](alloc::boxed::Box) via [`Into`], reusing its allocation.
+impl<T: ?Sized> From<alloc::boxed::Box<T>> for MyBox<T> {
+    #[inline]
+    fn from(boxed: alloc::boxed::Box<T>) -> Self {
+        Self::from_box(boxed)
+    }
+}
+
+/// Rust-specific helper to serialize a box as its contents.
+#[cfg(feature = "serde")]
+impl<T: ?Sized + serde::Serialize, A: Allocator> serde::Serialize for MyBox<T, A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        T::serialize(self, serializer)
+    }
+}
+
+/// Rust-specific helper to deserialize a box from its contents.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for MyBox<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_numbers() {
+        for i in [1, 2, 3] {
+            let boxed: MyBox<i32> = MyBox::new(i);
+            std::println!("my box stores {n}", n = *boxed);
+        }
+    }
+
+    #[test]
+    fn number_into_number() {
+        for i in [1, 2, 3] {
+            let boxed: MyBox<i32> = MyBox::new(i);
+            std::println!("my box unwraps into {n}", n = boxed.into_inner());
+            // boxed should not be double-freed
+        }
+    }
+
+    #[test]
+    fn store_unit_into_inner() {
+        // make sure we can handle zero-sized types correctly
+        let boxed: MyBox<()> = MyBox::new(());
+        std::println!("the box contains {unit:?}", unit = *boxed);
+        std::println!("the box unwraps into {unit:?}", unit = boxed.into_inner());
+    }
+
+    #[test]
+    fn store_unit_drop() {
         // make sure we can handle zero-sized types correctly
         let boxed: MyBox<()> = MyBox::new(());
         std::println!("the box contains {unit:?}", unit = *boxed);
         core::mem::drop(boxed);
         // should not double-free
     }
+
+    #[test]
+    fn send_to_thread() {
+        let boxed: MyBox<i32> = MyBox::new(5);
+        let handle = std::thread::spawn(move || boxed.into_inner());
+        assert_eq!(handle.join().unwrap(), 5);
+    }
+
+    #[test]
+    fn share_between_threads() {
+        let boxed: MyBox<i32> = MyBox::new(5);
+        let shared = &boxed;
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert_eq!(**shared, 5));
+            scope.spawn(|| assert_eq!(**shared, 5));
+        });
+    }
+
+    #[test]
+    fn compare_contents() {
+        // equal values in different allocations are still equal
+        assert_eq!(MyBox::new(5), MyBox::new(5));
+        assert_ne!(MyBox::new(5), MyBox::new(6));
+        assert!(MyBox::new(5) < MyBox::new(6));
+        assert_eq!(MyBox::new(7).cmp(&MyBox::new(6)), Ordering::Greater);
+        assert_eq!(MyBox::new(f64::NAN).partial_cmp(&MyBox::new(1.0)), None);
+    }
+
+    #[test]
+    fn hash_matches_eq() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let a = MyBox::new(std::string::String::from("hello"));
+        let b = MyBox::new(std::string::String::from("hello"));
+        assert_eq!(a, b);
+        assert_eq!(state.hash_one(&a), state.hash_one(&b));
+    }
+
+    #[test]
+    fn use_as_map_key() {
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(MyBox::new(2), "two");
+        map.insert(MyBox::new(1), "one");
+        assert_eq!(map.get(&MyBox::new(1)), Some(&"one"));
+        assert_eq!(
+            map.into_values().collect::<std::vec::Vec<_>>(),
+            ["one", "two"]
+        );
+    }
+
+    #[test]
+    fn format_contents() {
+        use std::format;
+
+        assert_eq!(format!("{}", MyBox::new("hello")), "hello");
+        assert_eq!(format!("{:>7}", MyBox::new("hello")), "  hello");
+        assert_eq!(format!("{:x}", MyBox::new(255)), "ff");
+        assert_eq!(format!("{:#X}", MyBox::new(255)), "0xFF");
+        assert_eq!(format!("{:o}", MyBox::new(8)), "10");
+        assert_eq!(format!("{:b}", MyBox::new(5)), "101");
+        assert_eq!(format!("{:e}", MyBox::new(1500.0)), "1.5e3");
+        assert_eq!(format!("{:E}", MyBox::new(1500.0)), "1.5E3");
+        assert_eq!(format!("{:?}", MyBox::new(5)), "MyBox(5)");
+    }
+
+    #[test]
+    fn try_new_succeeds() {
+        let boxed = MyBox::try_new(5).unwrap();
+        assert_eq!(*boxed, 5);
+    }
+
+    #[test]
+    fn try_new_returns_value_on_failure() {
+        let value = std::string::String::from("survivor");
+        let result = crate::test_alloc::failing_allocations(|| MyBox::try_new(value));
+        assert_eq!(result.unwrap_err().into_inner(), "survivor");
+    }
+
+    #[test]
+    fn try_new_fails_over_threshold() {
+        use crate::test_alloc::failing_allocations_over;
+
+        let small = failing_allocations_over(32, || MyBox::try_new(5_u32));
+        assert_eq!(*small.unwrap(), 5);
+        let large = failing_allocations_over(32, || MyBox::try_new([1_u8; 64]));
+        let error = large.unwrap_err();
+        assert_eq!(std::format!("{error}"), "memory allocation failed");
+        assert_eq!(error.into_inner(), [1; 64]);
+    }
+
+    #[test]
+    fn try_new_zst_never_fails() {
+        let result = crate::test_alloc::failing_allocations(|| MyBox::try_new(()));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn zst_dropped_once() {
+        use core::cell::Cell;
+
+        std::thread_local! {
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+        struct CountDrops;
+        impl Drop for CountDrops {
+            fn drop(&mut self) {
+                DROPS.set(DROPS.get() + 1);
+            }
+        }
+
+        let boxed = MyBox::new(CountDrops);
+        assert_eq!(DROPS.get(), 0);
+        core::mem::drop(boxed);
+        assert_eq!(DROPS.get(), 1);
+    }
+
+    #[test]
+    fn default_in_derived_struct() {
+        #[derive(Default)]
+        struct Buffers {
+            bytes: MyBox<std::vec::Vec<u8>>,
+            count: MyBox<i32>,
+        }
+
+        let buffers = Buffers::default();
+        assert!(buffers.bytes.is_empty());
+        assert_eq!(*buffers.count, 0);
+    }
+
+    #[test]
+    fn from_round_trip() {
+        let boxed: MyBox<i32> = 5.into();
+        assert_eq!(boxed.into_inner(), 5);
+        assert_eq!(MyBox::<&str>::from("hello").into_inner(), "hello");
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn new_uninit_then_init() {
+        let mut boxed = MyBox::<[u32; 64]>::new_uninit();
+        boxed.write([7; 64]);
+        // SAFETY: the value was just fully written
+        let boxed = unsafe { boxed.assume_init() };
+        assert!(boxed.iter().all(|&n| n == 7));
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn new_uninit_zst() {
+        let mut boxed = MyBox::<()>::new_uninit();
+        boxed.write(());
+        // SAFETY: the value was just fully written
+        let boxed = unsafe { boxed.assume_init() };
+        assert_eq!(boxed.into_inner(), ());
+    }
+
+    #[test]
+    fn new_uninit_drop_without_init() {
+        // dropping an uninitialized box must only free the allocation
+        let boxed = MyBox::<std::string::String>::new_uninit();
+        core::mem::drop(boxed);
+    }
+
+    #[test]
+    fn as_ref_in_generic_code() {
+        fn length<R: AsRef<std::vec::Vec<u8>>>(r: R) -> usize {
+            r.as_ref().len()
+        }
+        fn clear<R: AsMut<std::vec::Vec<u8>>>(mut r: R) -> R {
+            r.as_mut().clear();
+            r
+        }
+
+        let boxed = MyBox::new(std::vec![1, 2, 3]);
+        assert_eq!(length(&boxed), 3);
+        let boxed = clear(boxed);
+        assert_eq!(length(boxed), 0);
+    }
+
+    #[test]
+    fn borrow_for_map_lookup() {
+        use std::string::{String, ToString};
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(MyBox::new("one".to_string()), 1);
+        map.insert(MyBox::new("two".to_string()), 2);
+        assert_eq!(map.get(&String::from("two")), Some(&2));
+        assert_eq!(map.get(&String::from("three")), None);
+    }
+
+    #[test]
+    fn borrow_mut_in_generic_code() {
+        fn increment<B: BorrowMut<i32>>(mut b: B) -> B {
+            *b.borrow_mut() += 1;
+            b
+        }
+
+        assert_eq!(increment(MyBox::new(1)).into_inner(), 2);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn new_zeroed_is_zero() {
+        // SAFETY: all-zero bytes are a valid `[u8; 1024]`
+        let boxed = unsafe { MyBox::<[u8; 1024]>::new_zeroed().assume_init() };
+        assert!(boxed.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn new_zeroed_zst() {
+        // SAFETY: `()` has no bytes, so it is trivially valid
+        let boxed = unsafe { MyBox::<()>::new_zeroed().assume_init() };
+        assert_eq!(*boxed, ());
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn leak_outlives_box() {
+        let leaked: &'static mut i32 = MyBox::new(5).leak();
+        *leaked += 1;
+        assert_eq!(*leaked, 6);
+        // reclaim the allocation so Miri doesn't report the (intentional) leak
+        // SAFETY: `leaked` came from `leak`, is not used again, and is reclaimed only once
+        let reclaimed = unsafe { MyBox::from_raw(leaked) };
+        assert_eq!(reclaimed.into_inner(), 6);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn raw_round_trip() {
+        let raw = MyBox::new(std::string::String::from("round trip")).into_raw();
+        // SAFETY: `raw` came from `into_raw`, and is reclaimed only once
+        let boxed = unsafe { MyBox::from_raw(raw) };
+        assert_eq!(*boxed, "round trip");
+        // dropped normally here, exactly once
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn raw_round_trip_zst() {
+        let raw = MyBox::new(()).into_raw();
+        assert!(!raw.is_null());
+        // SAFETY: `raw` came from `into_raw`, and is reclaimed only once
+        let boxed = unsafe { MyBox::from_raw(raw) };
+        assert_eq!(boxed.into_inner(), ());
+    }
+
+    #[test]
+    fn clone_has_new_address() {
+        use std::format;
+
+        let boxed = MyBox::new(5);
+        let cloned = boxed.clone();
+        assert_ne!(boxed.as_ptr(), cloned.as_ptr());
+        assert_ne!(format!("{boxed:p}"), format!("{cloned:p}"));
+        assert_eq!(format!("{boxed:p}"), format!("{:p}", boxed.as_ptr()));
+    }
+
+    #[test]
+    fn pointer_formatting() {
+        use std::format;
+
+        let first = MyBox::new([1u8; 16]);
+        let second = MyBox::new([1u8; 16]);
+        assert_ne!(format!("{first:p}"), format!("{second:p}"));
+        assert!(format!("{first:p}").starts_with("0x"));
+        // formatting flags are passed through, and unsized boxes print the address of their data
+        assert_eq!(format!("{first:>40p}").trim_start(), format!("{first:p}"));
+        let slice: MyBox<[u8]> = MyBox::from_slice(&[1, 2, 3]);
+        assert_eq!(format!("{slice:p}"), format!("{:p}", slice.as_ptr()));
+    }
+
+    #[test]
+    fn named_accessors() {
+        /// Increment the value through the named accessors, then read it back.
+        fn bump<T: core::ops::AddAssign + Copy>(boxed: &mut MyBox<T>, one: T) -> T {
+            *MyBox::<T>::get_mut(boxed) += one;
+            *MyBox::<T>::get_ref(boxed)
+        }
+
+        let mut boxed = MyBox::new(1_u8);
+        assert_eq!(bump(&mut boxed, 1), 2);
+        assert_eq!(*MyBox::get_ref(&boxed), 2);
+
+        // slice methods of the same name aren't hidden
+        let mut slice: MyBox<[i32]> = MyBox::from_slice(&[1, 2, 3]);
+        *slice.get_mut(1).unwrap() = 20;
+        MyBox::get_mut(&mut slice)[2] = 30;
+        assert_eq!(MyBox::get_ref(&slice), [1, 20, 30]);
+        assert_eq!(slice.get(1), Some(&20));
+    }
+
+    #[test]
+    fn const_zst_box() {
+        use crate::test_alloc::count_allocations;
+
+        const BOXED: MyBox<()> = MyBox::new_zst(());
+        const EMPTY: MyBox<[std::string::String; 0]> = MyBox::new_zst([]);
+        let (boxed, allocations) = count_allocations(|| BOXED);
+        assert_eq!(allocations, 0);
+        assert_eq!(boxed.into_inner(), ());
+        assert!(EMPTY.is_empty());
+        // the dangling pointer is still aligned for the type
+        assert_eq!(EMPTY.as_ptr().addr() % align_of::<std::string::String>(), 0);
+    }
+
+    #[test]
+    fn zst_pointer_is_stable() {
+        let mut boxed = MyBox::new(());
+        let ptr = boxed.as_ptr();
+        assert!(!ptr.is_null());
+        assert_eq!(boxed.as_ptr(), ptr);
+        assert_eq!(boxed.as_mut_ptr().cast_const(), ptr);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn raw_round_trip_drops_once() {
+        use crate::test_drop::CountDrops;
+
+        let drops = core::cell::Cell::new(0);
+        let raw = MyBox::new(CountDrops::new(42, &drops)).into_raw();
+        assert_eq!(drops.get(), 0);
+        // SAFETY: `raw` came from `into_raw`, and is reclaimed only once
+        let boxed = unsafe { MyBox::from_raw(raw) };
+        assert_eq!(boxed.value, 42);
+        core::mem::drop(boxed);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn as_ptr_matches_reference() {
+        let mut boxed = MyBox::new(5);
+        assert_eq!(boxed.as_ptr(), &raw const *boxed);
+        assert_eq!(boxed.as_mut_ptr(), &raw mut *boxed);
+
+        let mut unit = MyBox::new(());
+        assert_eq!(unit.as_ptr(), &raw const *unit);
+        assert_eq!(unit.as_mut_ptr(), &raw mut *unit);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn assume_init_reuses_allocation() {
+        let mut boxed = MyBox::<[u64; 1024]>::new_uninit();
+        let before = boxed.as_ptr().cast::<[u64; 1024]>();
+        boxed.write([3; 1024]);
+        // SAFETY: the value was just fully written
+        let boxed = unsafe { boxed.assume_init() };
+        assert_eq!(boxed.as_ptr(), before);
+        assert!(boxed.iter().all(|&n| n == 3));
+
+        let zeroed = MyBox::<[u64; 1024]>::new_zeroed();
+        let before = zeroed.as_ptr().cast::<[u64; 1024]>();
+        // SAFETY: all-zero bytes are a valid `[u64; 1024]`
+        let zeroed = unsafe { zeroed.assume_init() };
+        assert_eq!(zeroed.as_ptr(), before);
+        assert!(zeroed.iter().all(|&n| n == 0));
+    }
+
+    #[test]
+    fn pin_self_referential_future() {
+        use core::{
+            future::Future,
+            pin::Pin,
+            task::{Context, Poll, Waker},
+        };
+
+        /// Returns `Pending` once, so the async block below has to be suspended while it holds a self-reference.
+        struct YieldOnce(bool);
+        impl Future for YieldOnce {
+            type Output = ();
+            fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    Poll::Ready(())
+                } else {
+                    self.0 = true;
+                    Poll::Pending
+                }
+            }
+        }
+
+        let mut future = MyBox::pin(async {
+            let value = 21;
+            let reference = &value;
+            YieldOnce(false).await;
+            *reference * 2
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(42));
+    }
+
+    #[test]
+    fn into_pin_unpin_round_trip() {
+        let pinned = MyBox::new(5).into_pin();
+        assert_eq!(*pinned, 5);
+        assert_eq!(core::pin::Pin::into_inner(pinned).into_inner(), 5);
+    }
+
+    #[test]
+    fn sort_and_compare_to_value() {
+        let mut boxes = [MyBox::new(3), MyBox::new(1), MyBox::new(2)];
+        boxes.sort();
+        assert_eq!(boxes, [MyBox::new(1), MyBox::new(2), MyBox::new(3)]);
+        assert!(MyBox::new(5) == 5);
+        assert!(MyBox::new(5) != 6);
+    }
+
+    #[test]
+    fn slice_from_slice() {
+        use crate::test_alloc::{count_allocations, count_deallocations};
+
+        let boxed = MyBox::from_slice(&[1, 2, 3]);
+        assert_eq!(*boxed, [1, 2, 3]);
+        assert_eq!(boxed.clone(), boxed);
+        assert_eq!(std::format!("{boxed:?}"), "MyBox([1, 2, 3])");
+
+        // exactly one allocation for the whole slice, freed exactly once
+        let (boxed, allocations) = count_allocations(|| MyBox::from_slice(&[1, 2, 3]));
+        assert_eq!(allocations, 1);
+        let ((), deallocations) = count_deallocations(|| core::mem::drop(boxed));
+        assert_eq!(deallocations, 1);
+    }
+
+    #[test]
+    fn slice_from_fn() {
+        let boxed = MyBox::from_fn(5, |i| i * i);
+        assert_eq!(*boxed, [0, 1, 4, 9, 16]);
+    }
+
+    #[test]
+    fn slice_from_iter() {
+        // exact size hint
+        let boxed: MyBox<[i32]> = (1..=3).collect();
+        assert_eq!(*boxed, [1, 2, 3]);
+        // no useful size hint, so the slice has to grow (and then shrink to fit)
+        let boxed: MyBox<[i32]> = (0..100).filter(|n| n % 3 == 0).collect();
+        assert_eq!(boxed.len(), 34);
+        assert!(boxed.iter().copied().eq((0..100).step_by(3)));
+    }
+
+    #[test]
+    fn empty_slice_does_not_allocate() {
+        use crate::test_alloc::failing_allocations;
+
+        let boxed = failing_allocations(|| MyBox::<[std::string::String]>::from_slice(&[]));
+        assert!(boxed.is_empty());
+        let boxed: MyBox<[i32]> = failing_allocations(|| core::iter::empty().collect());
+        assert!(boxed.is_empty());
+    }
+
+    #[test]
+    fn zst_slice() {
+        use crate::test_alloc::failing_allocations;
+
+        let boxed: MyBox<[()]> = failing_allocations(|| core::iter::repeat_n((), 1000).collect());
+        assert_eq!(boxed.len(), 1000);
+        let cloned = failing_allocations(|| boxed.clone());
+        assert_eq!(cloned.len(), 1000);
+    }
+
+    #[test]
+    fn slice_drops_each_element_once() {
+        use crate::test_drop::CountDrops;
+
+        let drops = core::cell::Cell::new(0);
+        let boxed: MyBox<[_]> = (0..10).map(|i| CountDrops::new(i, &drops)).collect();
+        assert_eq!(drops.get(), 0);
+        assert!(boxed.iter().map(|counter| counter.value).eq(0..10));
+        core::mem::drop(boxed);
+        assert_eq!(drops.get(), 10);
+
+        // zero-sized elements still have to be dropped
+        let drops = core::cell::Cell::new(0);
+        let boxed = MyBox::from_fn(10, |_| CountDrops::new((), &drops));
+        core::mem::drop(boxed);
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn slice_from_fn_panic_drops_prefix() {
+        use crate::test_drop::CountDrops;
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let drops = core::cell::Cell::new(0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            MyBox::from_fn(10, |i| {
+                assert!(i < 5, "stop early");
+                CountDrops::new(i, &drops)
+            })
+        }));
+        assert!(result.is_err());
+        // the 5 finished values were dropped exactly once, and nothing else was touched
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn str_from_str() {
+        use std::format;
+
+        let boxed = MyBox::<str>::from("héllo, wörld ✨");
+        assert_eq!(&*boxed, "héllo, wörld ✨");
+        assert_eq!(boxed.chars().count(), 14);
+        assert_eq!(format!("{boxed}"), "héllo, wörld ✨");
+        assert_eq!(format!("{boxed:?}"), "MyBox(\"héllo, wörld ✨\")");
+        assert_eq!(boxed.clone(), boxed);
+    }
+
+    #[test]
+    fn str_bytes_round_trip() {
+        let boxed = MyBox::<str>::from("ünïcödé");
+        let ptr = boxed.as_ptr().cast::<u8>();
+        let bytes = MyBox::<[u8]>::from(boxed);
+        assert_eq!(*bytes, *"ünïcödé".as_bytes());
+        let boxed = MyBox::from_utf8(bytes).unwrap();
+        assert_eq!(&*boxed, "ünïcödé");
+        // the allocation is reused in both directions
+        assert_eq!(boxed.as_ptr().cast::<u8>(), ptr);
+    }
+
+    #[test]
+    fn str_rejects_invalid_utf8() {
+        let bytes = MyBox::from_slice(&[b'o', b'k', 0xff, 0xfe]);
+        let error = MyBox::from_utf8(bytes).unwrap_err();
+        assert_eq!(error.valid_up_to(), 2);
+    }
+
+    #[test]
+    fn empty_str_does_not_allocate() {
+        let boxed = crate::test_alloc::failing_allocations(|| MyBox::<str>::from(""));
+        assert!(boxed.is_empty());
+    }
+
+    #[test]
+    fn trait_object_closure() {
+        use crate::test_drop::CountDrops;
+        use std::boxed::Box;
+
+        let drops = core::cell::Cell::new(0);
+        let captured = CountDrops::new(40, &drops);
+        let boxed: MyBox<dyn Fn() -> i32> = MyBox::from_box(Box::new(move || {
+            // borrow all of `captured`, so the closure takes ownership of it (and not just its `value` field)
+            let captured = &captured;
+            captured.value + 2
+        }));
+        assert_eq!(boxed(), 42);
+        assert_eq!(boxed(), 42);
+        assert_eq!(drops.get(), 0);
+        // dropping the trait object runs the closure's real destructor, which drops what it captured
+        core::mem::drop(boxed);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn trait_object_heterogeneous() {
+        use std::{boxed::Box, string::ToString};
+
+        /// Zero-sized, so neither `Box` nor `MyBox` allocates for it.
+        struct Unit;
+        impl fmt::Display for Unit {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("unit")
+            }
+        }
+
+        let displays: [MyBox<dyn fmt::Display>; 3] = [
+            MyBox::from_box(Box::new(1)),
+            MyBox::from_box(Box::new("two")),
+            MyBox::from_box(Box::new(Unit)),
+        ];
+        let rendered: std::vec::Vec<_> = displays.iter().map(ToString::to_string).collect();
+        assert_eq!(rendered, ["1", "two", "unit"]);
+    }
+
+    #[test]
+    fn downcast_any() {
+        use core::any::Any;
+        use std::{boxed::Box, rc::Rc};
+
+        let tracker = Rc::new(());
+        let boxed: MyBox<dyn Any> = MyBox::from_box(Box::new(Rc::clone(&tracker)));
+        let ptr = boxed.as_ptr().cast::<()>();
+        assert!(boxed.downcast_ref::<i32>().is_none());
+
+        // the wrong type gives back the original box, still holding the value
+        let boxed = boxed.downcast::<i32>().unwrap_err();
+        assert_eq!(Rc::strong_count(&tracker), 2);
+
+        // the right type reuses the allocation
+        let concrete = boxed.downcast::<Rc<()>>().unwrap();
+        assert_eq!(concrete.as_ptr().cast::<()>(), ptr);
+        assert_eq!(Rc::strong_count(&tracker), 2);
+        core::mem::drop(concrete);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn downcast_any_send() {
+        use core::any::Any;
+        use std::{boxed::Box, string::String};
+
+        let boxed: MyBox<dyn Any + Send> = MyBox::from_box(Box::new(42_i32));
+        // the trait object can cross threads, and still be downcast on the other side
+        let boxed = std::thread::spawn(move || boxed).join().unwrap();
+        let boxed = boxed.downcast::<String>().unwrap_err();
+        assert_eq!(boxed.downcast::<i32>().unwrap().into_inner(), 42);
+    }
+
+    #[test]
+    fn downcast_any_in_place() {
+        use core::any::Any;
+        use std::{boxed::Box, string::String};
+
+        let mut boxed: MyBox<dyn Any + Send> = MyBox::from_box(Box::new(String::from("hi")));
+        boxed.downcast_mut::<String>().unwrap().push('!');
+        assert_eq!(boxed.downcast_ref::<String>().unwrap(), "hi!");
+        assert!(boxed.downcast_mut::<i32>().is_none());
+        // dropping the trait object frees the string too (checked under Miri)
+        core::mem::drop(boxed);
+    }
+
+    #[test]
+    fn clone_from_reuses_allocation() {
+        use crate::test_alloc::count_allocations;
+
+        let source = MyBox::new([7_u8; 4096]);
+        let mut target = MyBox::new([0_u8; 4096]);
+        let ptr = target.as_ptr();
+        for _ in 0..10 {
+            let ((), allocations) = count_allocations(|| target.clone_from(&source));
+            assert_eq!(allocations, 0);
+        }
+        assert_eq!(target.as_ptr(), ptr);
+        assert_eq!(target, source);
+
+        let source = MyBox::from_slice(&[1, 2, 3]);
+        let mut target = MyBox::from_slice(&[4, 5, 6]);
+        let ((), allocations) = count_allocations(|| target.clone_from(&source));
+        assert_eq!(allocations, 0);
+        assert_eq!(target, source);
+
+        // `Vec::clone_from` keeps its own buffer too, as long as it has room
+        let source = MyBox::new(std::vec![1, 2, 3]);
+        let mut target = MyBox::new(std::vec![4, 5, 6, 7]);
+        let ptrs = (target.as_ptr(), target.as_ptr_range().start);
+        let ((), allocations) = count_allocations(|| target.clone_from(&source));
+        assert_eq!(allocations, 0);
+        assert_eq!((target.as_ptr(), target.as_ptr_range().start), ptrs);
+        assert_eq!(target, source);
+    }
+
+    #[test]
+    fn clone_from_drops_old_value_once() {
+        use crate::test_drop::CountDrops;
+
+        let drops = core::cell::Cell::new(0);
+        let source = MyBox::new(CountDrops::new(1, &drops));
+        let mut target = MyBox::new(CountDrops::new(2, &drops));
+        target.clone_from(&source);
+        // the old value in `target` was replaced (and dropped) exactly once
+        assert_eq!(drops.get(), 1);
+        assert_eq!(target.value, 1);
+        core::mem::drop((source, target));
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn borrow_str_keys() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(MyBox::<str>::from("key"), 1);
+        map.insert(MyBox::<str>::from("other"), 2);
+        // `MyBox<str>: Borrow<str>`, so lookups don't need to allocate a box
+        assert_eq!(map.get("key"), Some(&1));
+        assert_eq!(map.get("missing"), None);
+        *map.get_mut("other").unwrap() += 1;
+        assert_eq!(map["other"], 3);
+    }
+
+    #[test]
+    fn compare_to_unboxed() {
+        use std::string::String;
+
+        assert!(MyBox::new(5) < 6);
+        assert!(MyBox::new(5) >= 5);
+        assert_eq!(MyBox::new(1.0).partial_cmp(&f64::NAN), None);
+
+        let boxed = MyBox::new(String::from("hello"));
+        assert!(boxed == "hello");
+        assert!(boxed != "world");
+        assert!(boxed == *"hello");
+        let world = String::from("world");
+        assert!(boxed != world);
+        assert!(boxed < world);
+    }
+
+    #[test]
+    fn pinned_address_is_stable() {
+        use core::{marker::PhantomPinned, pin::Pin};
+
+        /// Never `Unpin`, so it can only be accessed through the pin.
+        struct Pinned(i32, PhantomPinned);
+
+        let pinned = MyBox::pin(Pinned(5, PhantomPinned));
+        let address: *const Pinned = &*pinned;
+        // moving the `Pin<MyBox<_>>` itself (here, into a new binding and through a function) doesn't move the value
+        let moved = core::convert::identity(pinned);
+        let mut moved_again = [moved];
+        let moved_address: *const Pinned = &*moved_again[0];
+        assert_eq!(address, moved_address);
+        assert_eq!(Pin::as_ref(&moved_again[0]).0, 5);
+        let _: Pin<&mut Pinned> = moved_again[0].as_mut();
+    }
+
+    #[test]
+    fn replace_take_swap_in_place() {
+        use crate::test_alloc::count_allocations;
+        use crate::test_drop::CountDrops;
+
+        let drops = core::cell::Cell::new(0);
+        let mut a = MyBox::new(CountDrops::new(1, &drops));
+        let mut b = MyBox::new(CountDrops::new(2, &drops));
+
+        let (old, allocations) = count_allocations(|| a.replace(CountDrops::new(3, &drops)));
+        assert_eq!(allocations, 0);
+        assert_eq!((old.value, a.value), (1, 3));
+        assert_eq!(drops.get(), 0);
+        core::mem::drop(old);
+        assert_eq!(drops.get(), 1);
+
+        let ptrs = (a.as_ptr(), b.as_ptr());
+        let ((), allocations) = count_allocations(|| MyBox::swap(&mut a, &mut b));
+        assert_eq!(allocations, 0);
+        assert_eq!((a.value, b.value), (2, 3));
+        assert_eq!((b.as_ptr(), a.as_ptr()), ptrs);
+        assert_eq!(drops.get(), 1);
+
+        core::mem::drop((a, b));
+        assert_eq!(drops.get(), 3);
+
+        let mut boxed = MyBox::new(std::vec![1, 2, 3]);
+        assert_eq!(boxed.take(), [1, 2, 3]);
+        assert!(boxed.is_empty());
+    }
+
+    #[test]
+    fn swap_large_values_by_pointer() {
+        let mut a = MyBox::new([1_u8; 4096]);
+        let mut b = MyBox::new([2_u8; 4096]);
+        let ptrs = (a.as_ptr(), b.as_ptr());
+        MyBox::swap(&mut a, &mut b);
+        // the arrays stayed where they were, only the boxes' pointers moved
+        assert_eq!((b.as_ptr(), a.as_ptr()), ptrs);
+        assert!(a.iter().all(|&byte| byte == 2));
+        assert!(b.iter().all(|&byte| byte == 1));
+
+        // ZSTs share the same dangling pointer, so swapping them changes nothing
+        let mut x = MyBox::new(());
+        let mut y = MyBox::new(());
+        MyBox::swap(&mut x, &mut y);
+        assert_eq!(x.as_ptr(), y.as_ptr());
+    }
+
+    #[test]
+    fn swap_does_not_hide_slice_swap() {
+        let mut boxed = MyBox::from_slice(&[1, 2, 3]);
+        boxed.swap(0, 2);
+        assert_eq!(*boxed, [3, 2, 1]);
+    }
+
+    #[test]
+    fn map_to_different_type() {
+        let boxed = MyBox::new(42).map(|n| std::format!("{n}!"));
+        assert_eq!(*boxed, "42!");
+    }
+
+    #[test]
+    fn map_same_layout_reuses_allocation() {
+        use crate::test_alloc::count_allocations;
+
+        let boxed = MyBox::new(21_u64);
+        let ptr = boxed.as_ptr();
+        let (boxed, allocations) = count_allocations(|| boxed.map(|n| n * 2));
+        assert_eq!(allocations, 0);
+        assert_eq!(*boxed, 42);
+        assert_eq!(boxed.as_ptr(), ptr);
+
+        // different types with the same layout can share the allocation too
+        let signed = boxed.map(|n| -i64::try_from(n).unwrap());
+        assert_eq!(signed.as_ptr().cast::<u64>(), ptr);
+        assert_eq!(*signed, -42);
+    }
+
+    #[test]
+    fn map_panic_frees_allocation() {
+        use crate::test_drop::CountDrops;
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let drops = core::cell::Cell::new(0);
+        let boxed = MyBox::new(CountDrops::new(1, &drops));
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            boxed.map(|counter| -> CountDrops<'_, i32> {
+                panic!("mapping {} failed", counter.value)
+            })
+        }));
+        assert!(result.is_err());
+        // the value was dropped by the panicking closure, and the allocation was freed (checked under Miri)
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn try_map_ok_reuses_allocation() {
+        use crate::test_alloc::count_allocations;
+
+        let boxed = MyBox::new(42_u32);
+        let ptr = boxed.as_ptr();
+        let (result, allocations) = count_allocations(|| boxed.try_map(i32::try_from));
+        let signed = result.unwrap();
+        assert_eq!(allocations, 0);
+        assert_eq!(*signed, 42);
+        assert_eq!(signed.as_ptr().cast::<u32>(), ptr);
+
+        // a different layout needs a new allocation
+        let wide = signed.try_map(|n| Ok::<_, ()>(i64::from(n))).unwrap();
+        assert_eq!(*wide, 42);
+    }
+
+    #[test]
+    fn try_map_err_frees_once() {
+        use crate::test_alloc::count_deallocations;
+
+        // same layout: the reused allocation is freed on error
+        let boxed = MyBox::new(u32::MAX);
+        let (result, deallocations) = count_deallocations(|| boxed.try_map(i32::try_from));
+        assert!(result.is_err());
+        assert_eq!(deallocations, 1);
+
+        // different layout: the original allocation is freed before `f` even runs
+        let boxed = MyBox::new(-1_i64);
+        let (result, deallocations) = count_deallocations(|| boxed.try_map(u8::try_from));
+        assert!(result.is_err());
+        assert_eq!(deallocations, 1);
+    }
+
+    #[test]
+    fn try_map_counting_allocator() {
+        use crate::test_alloc::CountingAllocator;
+
+        let counter = CountingAllocator::default();
+        // `Ok` with the same layout keeps the one allocation, and a different layout swaps it for a new one
+        let same = MyBox::new_in(7_u32, &counter)
+            .try_map(i32::try_from)
+            .unwrap();
+        assert_eq!((counter.allocations(), counter.deallocations()), (1, 0));
+        let wider = same.try_map(|n| Ok::<_, ()>(i64::from(n) * 2)).unwrap();
+        assert_eq!((counter.allocations(), counter.deallocations()), (2, 1));
+        assert_eq!(*wider, 14);
+
+        // `Err` leaves nothing behind, whichever path was taken
+        let error = wider.try_map(|n| Err::<u8, _>(n + 1)).unwrap_err();
+        assert_eq!(error, 15);
+        let error = MyBox::new_in(-1_i64, &counter).try_map(u64::try_from);
+        assert!(error.is_err());
+        assert_eq!((counter.allocations(), counter.deallocations()), (3, 3));
+    }
+
+    #[test]
+    fn bump_allocations_are_balanced() {
+        use crate::test_alloc::{BumpAllocator, count_allocations};
+        use crate::test_drop::CountDrops;
+
+        let bump = BumpAllocator::<1024>::new();
+        let drops = core::cell::Cell::new(0);
+        // none of this should touch the global heap
+        let ((), allocations) = count_allocations(|| {
+            let a = MyBox::new_in(CountDrops::new(1_u64, &drops), &bump);
+            let b = MyBox::new_in(CountDrops::new(2_u64, &drops), &bump);
+            assert!(bump.contains(a.as_ptr()) && bump.contains(b.as_ptr()));
+            assert_eq!(bump.live(), 2);
+
+            assert_eq!(b.into_inner().value, 2);
+            assert_eq!(bump.live(), 1);
+            assert_eq!(a.value, 1);
+            drop(a);
+
+            // ZSTs are never allocated, even in a custom allocator
+            let unit = MyBox::new_in((), &bump);
+            assert_eq!(*unit, ());
+        });
+        assert_eq!(allocations, 0);
+        assert_eq!(bump.live(), 0);
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn bump_behaves_like_global() {
+        use crate::test_alloc::BumpAllocator;
+
+        let bump = BumpAllocator::<1024>::new();
+        let mut local = MyBox::new_in(std::vec![1, 2, 3], &bump);
+        let global = MyBox::new(std::vec![1, 2, 3]);
+        assert_eq!(*local, *global);
+        assert_eq!(std::format!("{local:?}"), std::format!("{global:?}"));
+
+        local.push(4);
+        assert_eq!(local.replace(std::vec![5]), [1, 2, 3, 4]);
+        let cloned = local.clone();
+        assert!(bump.contains(cloned.as_ptr()));
+        assert_eq!(cloned, local);
+
+        // same layout reuses the slot, different layout moves to a new one in the same allocator
+        let mapped = MyBox::new_in(7_u32, &bump).map(|n| -(n as i32));
+        assert_eq!(*mapped, -7);
+        let widened = mapped.map(i64::from);
+        assert!(bump.contains(widened.as_ptr()));
+        assert_eq!(*widened, -7);
+
+        drop((local, cloned, widened));
+        assert_eq!(bump.live(), 0);
+    }
+
+    #[test]
+    fn counting_allocator_balanced() {
+        use crate::test_alloc::{CountingAllocator, count_allocations};
+
+        let counter = CountingAllocator::default();
+        let ((), global_allocations) = count_allocations(|| {
+            let a = MyBox::new_in(5_u64, &counter);
+            let b = a.clone();
+            assert_eq!(counter.allocations(), 2);
+            assert_eq!(b.into_inner(), 5);
+            assert_eq!(counter.deallocations(), 1);
+
+            // a different layout frees the old slot and allocates a new one, both through the same allocator
+            let c = a.map(|n| n as u8);
+            assert_eq!(*c, 5);
+            assert_eq!((counter.allocations(), counter.deallocations()), (3, 2));
+        });
+        // every heap allocation went through the counting allocator, and was given back
+        assert_eq!(global_allocations, counter.allocations());
+        assert_eq!(counter.allocations(), counter.deallocations());
+    }
+
+    #[test]
+    fn bump_allocator_full() {
+        use crate::test_alloc::BumpAllocator;
+
+        let bump = BumpAllocator::<16>::new();
+        let first = MyBox::try_new_in([1_u8; 12], &bump).unwrap();
+        let error = MyBox::try_new_in([2_u8; 8], &bump).unwrap_err();
+        assert_eq!(error.into_inner(), [2; 8]);
+        assert_eq!(*first, [1; 12]);
+        drop(first);
+        assert_eq!(bump.live(), 0);
+    }
+
+    #[test]
+    fn iterate_through_box() {
+        let values = [1, 2, 3, 4, 5];
+        let mut boxed = MyBox::new(values.iter());
+        assert_eq!(boxed.size_hint(), values.iter().size_hint());
+        assert_eq!(boxed.next(), Some(&1));
+        assert_eq!(boxed.next_back(), Some(&5));
+        assert_eq!(boxed.len(), 3);
+        assert_eq!(boxed.size_hint(), (3, Some(3)));
+        assert_eq!(
+            boxed.rev().copied().collect::<std::vec::Vec<_>>(),
+            [4, 3, 2]
+        );
+
+        // boxed trait objects can be returned from functions and still iterated
+        fn evens(limit: i32) -> MyBox<dyn Iterator<Item = i32>> {
+            MyBox::from_box(std::boxed::Box::new((0..limit).filter(|n| n % 2 == 0)))
+        }
+        assert_eq!(evens(7).sum::<i32>(), 12);
+    }
+
+    #[test]
+    fn filled_with_large_array() {
+        use crate::test_alloc::{CountingAllocator, count_allocations};
+
+        let (boxed, allocations) = count_allocations(|| MyBox::filled_with(|| [0u8; 8192]));
+        assert_eq!(allocations, 1);
+        assert!(boxed.iter().all(|&byte| byte == 0));
+
+        // a panicking initializer frees the allocation, and leaves nothing to drop
+        let counter = CountingAllocator::default();
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            MyBox::<[u8; 8192], _>::filled_with_in(|| panic!("initializer panicked"), &counter)
+        }));
+        assert!(result.is_err());
+        assert_eq!((counter.allocations(), counter.deallocations()), (1, 1));
+    }
+
+    #[test]
+    fn collect_boxed_range() {
+        let boxed = MyBox::new(0..5);
+        assert_eq!(boxed.len(), 5);
+        assert_eq!(boxed.clone().collect::<std::vec::Vec<_>>(), [0, 1, 2, 3, 4]);
+        // `fold` (and everything built on it) runs on the unboxed range
+        assert_eq!(boxed.clone().fold(0, |sum, n| sum * 10 + n), 1234);
+        assert_eq!(boxed.rev().collect::<std::vec::Vec<_>>(), [4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn await_boxed_futures() {
+        let mut cx = Context::from_waker(core::task::Waker::noop());
+
+        let mut ready = MyBox::new(core::future::ready(42));
+        assert_eq!(Pin::new(&mut ready).poll(&mut cx), Poll::Ready(42));
+
+        // `async` blocks aren't `Unpin`, so they are pinned first, and can then be boxed again (or awaited) like any future
+        let mut outer = MyBox::pin(async { MyBox::new(MyBox::pin(async { 42 })).await + 1 });
+        assert_eq!(outer.as_mut().poll(&mut cx), Poll::Ready(43));
+    }
+
+    #[test]
+    fn block_on_boxed_dyn_future() {
+        /// Minimal executor: poll the future until it is ready, with a waker that does nothing.
+        fn block_on<F: Future>(future: F) -> F::Output {
+            let mut future = core::pin::pin!(future);
+            let mut cx = Context::from_waker(core::task::Waker::noop());
+            loop {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return output;
+                }
+            }
+        }
+
+        let boxed: MyBox<dyn Future<Output = i32> + Unpin> =
+            MyBox::from_box(std::boxed::Box::new(core::future::ready(7)));
+        assert_eq!(block_on(boxed), 7);
+        assert_eq!(
+            block_on(async { MyBox::new(core::future::ready(6)).await * 7 }),
+            42
+        );
+    }
+
+    #[test]
+    fn box_round_trip() {
+        use crate::test_alloc::count_allocations;
+        use crate::test_drop::CountDrops;
+
+        let drops = core::cell::Cell::new(0);
+        let boxed = MyBox::new(CountDrops::new(42, &drops));
+        let ptr = boxed.as_ptr();
+        let (std_box, allocations) = count_allocations(|| boxed.into_box());
+        assert_eq!(allocations, 0);
+        assert_eq!((&raw const *std_box, std_box.value), (ptr, 42));
+
+        let (boxed, allocations) =
+            count_allocations(|| MyBox::<CountDrops<'_, i32>>::from(std_box));
+        assert_eq!(allocations, 0);
+        assert_eq!((boxed.as_ptr(), boxed.value), (ptr, 42));
+        assert_eq!(drops.get(), 0);
+        core::mem::drop(boxed);
+        assert_eq!(drops.get(), 1);
+
+        // zero-sized values and unsized values convert the same way
+        // (the target type is needed, since `MyBox<Box<T>>` can also be made from a `Box<T>`)
+        let unit = MyBox::new(()).into_box();
+        assert_eq!(MyBox::<()>::from(unit), ());
+        let slice = MyBox::from_slice(&[1, 2, 3]).into_box();
+        assert_eq!(*MyBox::<[i32]>::from(slice), [1, 2, 3]);
+        let empty = MyBox::<str>::from("").into_box();
+        assert_eq!(&*MyBox::<str>::from(empty), "");
+    }
+
+    #[test]
+    fn box_conversions_drop_once() {
+        use crate::test_drop::CountDrops;
+        use std::boxed::Box;
+
+        let drops = core::cell::Cell::new(0);
+        let slice = MyBox::from_fn(3, |i| CountDrops::new(i, &drops));
+        let ptr = slice.as_ptr();
+        let std_box = slice.into_box();
+        assert_eq!(&raw const *std_box, ptr);
+        let slice = MyBox::<[CountDrops<'_, usize>]>::from(std_box);
+        assert_eq!(slice.as_ptr(), ptr);
+        assert_eq!(drops.get(), 0);
+        core::mem::drop(slice);
+        assert_eq!(drops.get(), 3);
+
+        // both sides use the same dangling pointer for ZSTs, so it maps across unchanged
+        let unit = MyBox::new(());
+        let ptr = unit.as_ptr();
+        assert_eq!(&raw const *unit.into_box(), ptr);
+        assert_eq!(MyBox::<()>::from(Box::new(())).as_ptr(), ptr);
+    }
+
+    #[test]
+    fn error_source_chain() {
+        use std::{string::ToString, vec::Vec};
+
+        #[derive(Debug)]
+        struct Inner;
+        impl fmt::Display for Inner {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("inner failure")
+            }
+        }
+        impl Error for Inner {}
+
+        #[derive(Debug)]
+        struct Outer(MyBox<dyn Error + Send + Sync>);
+        impl fmt::Display for Outer {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("outer failure")
+            }
+        }
+        impl Error for Outer {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        // `?` boxes the callee's error
+        fn inner() -> Result<(), MyBox<Inner>> {
+            Err(Inner)?
+        }
+        fn outer() -> Result<(), MyBox<Outer>> {
+            let source = inner().unwrap_err();
+            Err(Outer(MyBox::from_box(std::boxed::Box::new(source))))?
+        }
+
+        let error = outer().unwrap_err();
+        let mut chain = Vec::new();
+        let mut current: Option<&(dyn Error + 'static)> = Some(&error);
+        while let Some(error) = current {
+            chain.push(error.to_string());
+            current = error.source();
+        }
+        assert_eq!(chain, ["outer failure", "inner failure"]);
+    }
+
+    #[test]
+    fn error_forwards_through_box() {
+        #[derive(Debug)]
+        struct Parse(core::num::ParseIntError);
+        impl fmt::Display for Parse {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("bad config value")
+            }
+        }
+        impl Error for Parse {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+
+            fn description(&self) -> &str {
+                "parse"
+            }
+        }
+
+        let cause = "x".parse::<u8>().unwrap_err();
+        let boxed: MyBox<dyn Error> = MyBox::from_box(std::boxed::Box::new(Parse(cause.clone())));
+        assert_eq!(std::format!("{boxed}"), "bad config value");
+        let source = boxed.source().unwrap();
+        assert_eq!(source.downcast_ref(), Some(&cause));
+        #[allow(
+            deprecated,
+            reason = "checking that the deprecated method is forwarded too"
+        )]
+        let description = boxed.description();
+        assert_eq!(description, "parse");
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn unsized_coercions() {
+        use crate::test_drop::CountDrops;
+
+        trait Shape {
+            fn area(&self) -> u32;
+            fn into_area(self: MyBox<Self>) -> u32;
+        }
+        struct Square(u32);
+        impl Shape for Square {
+            fn area(&self) -> u32 {
+                self.0 * self.0
+            }
+            fn into_area(self: MyBox<Self>) -> u32 {
+                self.area()
+            }
+        }
+
+        let shape: MyBox<dyn Shape> = MyBox::new(Square(3));
+        assert_eq!(shape.area(), 9);
+        assert_eq!(shape.into_area(), 9);
+
+        let drops = core::cell::Cell::new(0);
+        let array = MyBox::new([0, 1, 2].map(|i| CountDrops::new(i, &drops)));
+        let slice: MyBox<[CountDrops<'_, i32>]> = array;
+        assert_eq!(slice.len(), 3);
+        assert_eq!(slice[2].value, 2);
+        core::mem::drop(slice);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn slice_into_iter() {
+        let boxed = MyBox::from_slice(&[1, 2, 3, 4]);
+        assert_eq!(boxed.clone().into_iter().sum::<i32>(), 10);
+        assert_eq!(
+            boxed
+                .clone()
+                .into_iter()
+                .rev()
+                .collect::<std::vec::Vec<_>>(),
+            [4, 3, 2, 1]
+        );
+
+        let mut iter = boxed.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!((iter.next(), iter.next_back()), (Some(1), Some(4)));
+        assert_eq!(iter.as_slice(), [2, 3]);
+        assert_eq!(std::format!("{iter:?}"), "IntoIter([2, 3])");
+        assert_eq!(
+            (iter.next(), iter.next(), iter.next()),
+            (Some(2), Some(3), None)
+        );
+        assert_eq!(iter.next_back(), None);
+
+        let mut boxed = MyBox::from_slice(&[1, 2, 3]);
+        for value in &mut boxed {
+            *value *= 10;
+        }
+        assert_eq!((&boxed).into_iter().sum::<i32>(), 60);
+    }
+
+    #[test]
+    fn slice_into_iter_drops_rest() {
+        use crate::test_alloc::count_deallocations;
+        use crate::test_drop::CountDrops;
+
+        let drops = core::cell::Cell::new(0);
+        let boxed = MyBox::from_fn(6, |i| CountDrops::new(i, &drops));
+        let mut iter = boxed.into_iter();
+        let (first, last) = (iter.next().unwrap(), iter.next_back().unwrap());
+        assert_eq!((first.value, last.value), (0, 5));
+        core::mem::drop((first, last));
+        assert_eq!(drops.get(), 2);
+
+        // the 4 values left in the middle are dropped with the iterator, along with the allocation
+        let ((), deallocations) = count_deallocations(|| core::mem::drop(iter));
+        assert_eq!(deallocations, 1);
+        assert_eq!(drops.get(), 6);
+
+        // ZSTs and empty slices work the same way
+        assert_eq!(MyBox::<[()]>::from_fn(3, |_| ()).into_iter().count(), 3);
+        assert_eq!(MyBox::<[i32]>::from_slice(&[]).into_iter().next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn call_boxed_closures() {
+        let double: MyBox<dyn Fn(i32) -> i32> = MyBox::new(|n| n * 2);
+        assert_eq!(double(21), 42);
+        // boxed closures can be passed anywhere a closure is expected
+        assert_eq!([1, 2].map(&double), [2, 4]);
+
+        let mut total = 0;
+        let mut add: MyBox<dyn FnMut(i32) + '_> = MyBox::new(|n| total += n);
+        add(1);
+        add(2);
+        core::mem::drop(add);
+        assert_eq!(total, 3);
+
+        // closures that can only be called once are moved out of the box
+        fn call_once<R>(f: impl FnOnce() -> R) -> R {
+            f()
+        }
+        let message = std::string::String::from("moved");
+        assert_eq!(call_once(MyBox::new(move || message)), "moved");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let nested = MyBox::new(MyBox::new(5));
+        let json = serde_json::to_string(&nested).unwrap();
+        assert_eq!(json, "5");
+        let back: MyBox<MyBox<i32>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, nested);
+
+        let unit = MyBox::new(());
+        let json = serde_json::to_string(&unit).unwrap();
+        assert_eq!(json, "null");
+        let back: MyBox<()> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, unit);
+
+        let slice = MyBox::from_slice(&[1, 2, 3]);
+        assert_eq!(serde_json::to_string(&slice).unwrap(), "[1,2,3]");
+    }
 }
@@ -0,0 +1,1092 @@
+//! Double-ended queue data structure.
+//!
+//! Values are stored in a ring buffer on the heap: the deque remembers where its front is, and wraps around to the
+//! start of the buffer when it runs off the end, so values can be pushed and popped at either end in amortized O(1).
+
+use crate::debug_values::DebugValues;
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+pub use implementation::MyDeque;
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use crate::raw_buf::RawBuf;
+    use core::{ops::Range, ptr};
+
+    /// Growable ring buffer of values, which can be pushed and popped at either end.
+    ///
+    /// Like [`alloc::collections::VecDeque`], zero-sized values never allocate, and such a deque can hold up to
+    /// `usize::MAX` of them.
+    pub struct MyDeque<T> {
+        /*
+        invariants:
+        - `len <= buf.capacity()`, and `head < buf.capacity()` (or `head == 0` if the capacity is 0)
+        - the value at logical index `i` is in the initialized slot `(head + i) % capacity`, for every `i < len`,
+          and every other slot is uninitialized
+        */
+        buf: RawBuf<T>,
+        head: usize,
+        len: usize,
+    }
+
+    /// Drops the values in a slice when it goes out of scope, so a panicking destructor still drops the other slice.
+    struct DropSlice<T>(*mut [T]);
+
+    impl<T> Drop for DropSlice<T> {
+        fn drop(&mut self) {
+            // SAFETY: `DropSlice` is only created for initialized values that the deque has already forgotten
+            unsafe { self.0.drop_in_place() };
+        }
+    }
+
+    impl<T> MyDeque<T> {
+        /// Create an empty deque, without allocating.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                buf: RawBuf::new(),
+                head: 0,
+                len: 0,
+            }
+        }
+
+        /// Create an empty deque with room for at least `capacity` values before it needs to reallocate.
+        ///
+        /// # Panics
+        ///
+        /// If the buffer would be larger than `isize::MAX` bytes.
+        #[inline]
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                buf: RawBuf::with_capacity(capacity),
+                head: 0,
+                len: 0,
+            }
+        }
+
+        /// Number of values in the deque.
+        #[doc(alias = "size")]
+        #[inline]
+        pub const fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Number of values the deque can hold before it needs to reallocate.
+        ///
+        /// For zero-sized types, this is always `usize::MAX`.
+        #[inline]
+        pub const fn capacity(&self) -> usize {
+            self.buf.capacity()
+        }
+
+        /// Physical slot of the value at logical index `index`, which must be at most the capacity.
+        #[inline]
+        fn to_physical(&self, index: usize) -> usize {
+            // can't overflow for sized types, since the capacity is at most `isize::MAX`; for zero-sized types, every
+            // slot is the same, so wrapping doesn't matter
+            let slot = self.head.wrapping_add(index);
+            if slot >= self.capacity() {
+                slot - self.capacity()
+            } else {
+                slot
+            }
+        }
+
+        /// Physical slots of the values, from the front to the end of the buffer, then wrapped around from its start.
+        fn physical_ranges(&self) -> (Range<usize>, Range<usize>) {
+            let front_len = (self.capacity() - self.head).min(self.len);
+            (self.head..self.head + front_len, 0..self.len - front_len)
+        }
+
+        /// Raw slice over the physical slots in `range`.
+        ///
+        /// # Safety
+        ///
+        /// `range` must be within the capacity.
+        unsafe fn raw_slice(&self, range: Range<usize>) -> *mut [T] {
+            // SAFETY: the caller guarantees that `range` is within the capacity
+            ptr::slice_from_raw_parts_mut(unsafe { self.buf.ptr().add(range.start) }, range.len())
+        }
+
+        /// Add `value` to the back of the deque, doubling the capacity if it is full.
+        ///
+        /// # Panics
+        ///
+        /// If the new capacity would be larger than `isize::MAX` bytes (or, for zero-sized types, if the length overflows).
+        pub fn push_back(&mut self, value: T) {
+            if self.len == self.capacity() {
+                self.grow();
+            }
+            let slot = self.to_physical(self.len);
+            // SAFETY: there is room for one more value, so the slot after the back is in bounds and uninitialized
+            unsafe { self.buf.ptr().add(slot).write(value) };
+            self.len += 1;
+        }
+
+        /// Add `value` to the front of the deque, doubling the capacity if it is full.
+        ///
+        /// # Panics
+        ///
+        /// If the new capacity would be larger than `isize::MAX` bytes (or, for zero-sized types, if the length overflows).
+        pub fn push_front(&mut self, value: T) {
+            if self.len == self.capacity() {
+                self.grow();
+            }
+            // the capacity is non-zero after growing, so this stays in bounds
+            self.head = self.head.checked_sub(1).unwrap_or(self.capacity() - 1);
+            // SAFETY: there was room for one more value, so the slot before the old front is in bounds and uninitialized
+            unsafe { self.buf.ptr().add(self.head).write(value) };
+            self.len += 1;
+        }
+
+        /// Remove the first value from the deque and return it, or `None` if it is empty.
+        pub fn pop_front(&mut self) -> Option<T> {
+            if self.len == 0 {
+                return None;
+            }
+            let slot = self.head;
+            self.head = self.to_physical(1);
+            self.len -= 1;
+            // SAFETY: the slot held the front value, and is now outside of the deque so it is never read again
+            Some(unsafe { self.buf.ptr().add(slot).read() })
+        }
+
+        /// Remove the last value from the deque and return it, or `None` if it is empty.
+        pub fn pop_back(&mut self) -> Option<T> {
+            if self.len == 0 {
+                return None;
+            }
+            self.len -= 1;
+            let slot = self.to_physical(self.len);
+            // SAFETY: the slot held the back value, and is now outside of the deque so it is never read again
+            Some(unsafe { self.buf.ptr().add(slot).read() })
+        }
+
+        /// Get a reference to the value at `index` (counting from the front), or `None` if it is out of bounds.
+        #[inline]
+        pub fn get(&self, index: usize) -> Option<&T> {
+            if index >= self.len {
+                return None;
+            }
+            // SAFETY: `index < len`, so its physical slot is initialized, and the reference borrows the deque
+            Some(unsafe { &*self.buf.ptr().add(self.to_physical(index)) })
+        }
+
+        /// Get a mutable reference to the value at `index` (counting from the front), or `None` if it is out of bounds.
+        #[inline]
+        pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+            if index >= self.len {
+                return None;
+            }
+            // SAFETY: `index < len`, so its physical slot is initialized, and the reference mutably borrows the deque
+            Some(unsafe { &mut *self.buf.ptr().add(self.to_physical(index)) })
+        }
+
+        /// View the values as two slices, which together hold every value from front to back.
+        ///
+        /// The second slice is only non-empty if the values wrap around the end of the buffer.
+        #[inline]
+        pub fn as_slices(&self) -> (&[T], &[T]) {
+            let (front, back) = self.physical_ranges();
+            // SAFETY: both ranges are within the capacity, and hold initialized values, which the slices borrow
+            unsafe { (&*self.raw_slice(front), &*self.raw_slice(back)) }
+        }
+
+        /// Mutably view the values as two slices, which together hold every value from front to back.
+        ///
+        /// The second slice is only non-empty if the values wrap around the end of the buffer.
+        #[inline]
+        pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+            let (front, back) = self.physical_ranges();
+            // SAFETY: both ranges are within the capacity, hold initialized values, and don't overlap, and the slices
+            // mutably borrow the deque
+            unsafe { (&mut *self.raw_slice(front), &mut *self.raw_slice(back)) }
+        }
+
+        /// Rearrange the values in place, so that they don't wrap around the end of the buffer, and view them as one slice.
+        ///
+        /// This never reallocates. If there is enough free room, each value is moved at most twice; otherwise,
+        /// the values are rotated into place within the buffer.
+        pub fn make_contiguous(&mut self) -> &mut [T] {
+            let cap = self.capacity();
+            let (front, back) = self.physical_ranges();
+            let (front_len, back_len) = (front.len(), back.len());
+            let free = cap - self.len;
+            let ptr = self.buf.ptr();
+            if back_len == 0 {
+                // already contiguous
+            } else if free >= front_len {
+                // SAFETY: with that much free room, `len <= head`, so shifting the back right by `front_len` keeps it
+                // before the front (`copy` allows it to overlap itself), and the front then fits in front of it
+                unsafe {
+                    ptr::copy(ptr, ptr.add(front_len), back_len);
+                    ptr::copy_nonoverlapping(ptr.add(self.head), ptr, front_len);
+                }
+                self.head = 0;
+            } else if free >= back_len {
+                let new_head = self.head - back_len;
+                // SAFETY: with that much free room, the front can shift left by `back_len` without reaching the back
+                // (`copy` allows it to overlap itself), and the back then fits after it, before the end of the buffer
+                unsafe {
+                    ptr::copy(ptr.add(self.head), ptr.add(new_head), front_len);
+                    ptr::copy_nonoverlapping(ptr, ptr.add(new_head + front_len), back_len);
+                }
+                self.head = new_head;
+            } else {
+                // SAFETY: closing the free gap between the back and the front leaves `0..len` initialized, as the back
+                // followed by the front (`copy` allows the front to overlap itself), and the slice borrows the deque
+                unsafe {
+                    ptr::copy(ptr.add(self.head), ptr.add(back_len), front_len);
+                    self.head = 0;
+                    (*self.raw_slice(0..self.len)).rotate_right(front_len);
+                }
+            }
+            // SAFETY: the values are now contiguous from `head`, and the slice mutably borrows the deque
+            unsafe { &mut *self.raw_slice(self.head..self.head + self.len) }
+        }
+
+        /// Rotate a full deque `n <= len` places to the left in O(1), or return `false` if the buffer has free slots.
+        ///
+        /// With no free slots, the back ends right before the front, so the values are already in rotated order from
+        /// slot `head + n`: moving the front there is enough.
+        pub(super) fn rotate_full_left(&mut self, n: usize) -> bool {
+            if self.len != self.capacity() {
+                return false;
+            }
+            self.head = self.to_physical(n);
+            true
+        }
+
+        /// Drop every value in the deque.
+        ///
+        /// The capacity is unchanged.
+        pub fn clear(&mut self) {
+            let (front, back) = self.physical_ranges();
+            // SAFETY: both ranges are within the capacity
+            let (front, back) = unsafe { (self.raw_slice(front), self.raw_slice(back)) };
+            // forget the values first, so that a panicking destructor can't lead to a double drop
+            self.head = 0;
+            self.len = 0;
+            // dropped even if dropping `front` panics
+            let _back = DropSlice(back);
+            // SAFETY: the values were initialized, and are now outside of the deque so they are never accessed again
+            unsafe { front.drop_in_place() };
+        }
+
+        /// Double the capacity, then move the wrapped-around values so that they are in order again.
+        ///
+        /// If the values were wrapped, whichever part is shorter is moved: the back (from the start of the buffer to
+        /// just after the old end), or the front (to the new end of the buffer).
+        #[cold]
+        fn grow(&mut self) {
+            let old_cap = self.capacity();
+            self.buf.reserve(self.len, 1);
+            let new_cap = self.capacity();
+            if self.head <= old_cap - self.len {
+                // not wrapped, so the values are still in order
+                return;
+            }
+            let front_len = old_cap - self.head;
+            let back_len = self.len - front_len;
+            let ptr = self.buf.ptr();
+            if back_len < front_len && back_len <= new_cap - old_cap {
+                // SAFETY: the back is initialized and starts at slot 0, and there is room for it after the old end,
+                // where nothing is initialized (and which can't overlap the old buffer)
+                unsafe { ptr::copy_nonoverlapping(ptr, ptr.add(old_cap), back_len) };
+            } else {
+                let new_head = new_cap - front_len;
+                // SAFETY: the front is initialized and ends at the old end, and the new end is later, so both are in
+                // bounds (`copy` allows them to overlap); the old slots that aren't overwritten are forgotten
+                unsafe { ptr::copy(ptr.add(self.head), ptr.add(new_head), front_len) };
+                self.head = new_head;
+            }
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Both parts of a wrapped-around deque are dropped, then the buffer is freed.
+    impl<T> Drop for MyDeque<T> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+}
+
+impl<T> MyDeque<T> {
+    /// Check whether the deque has no values.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a reference to the first value, or `None` if the deque is empty.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Get a mutable reference to the first value, or `None` if the deque is empty.
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Get a reference to the last value, or `None` if the deque is empty.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Get a mutable reference to the last value, or `None` if the deque is empty.
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len().checked_sub(1)?)
+    }
+}
+
+impl<T> MyDeque<T> {
+    /// Rotate the values `n` places to the left, so that the value at index `n` becomes the front.
+    ///
+    /// This never reallocates. If the buffer is full, only the front moves, in O(1); otherwise, it is
+    /// O(min(n, len - n)), with each step moving one value from one end to the other.
+    ///
+    /// # Panics
+    ///
+    /// If `n > len`.
+    #[track_caller]
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.len();
+        assert!(
+            n <= len,
+            "rotation amount (is {n}) should be <= len (is {len})"
+        );
+        if self.rotate_full_left(n) {
+            // already rotated
+        } else if n <= len - n {
+            self.rotate_front_to_back(n);
+        } else {
+            self.rotate_back_to_front(len - n);
+        }
+    }
+
+    /// Rotate the values `n` places to the right, so that the value at index `len - n` becomes the front.
+    ///
+    /// This never reallocates. If the buffer is full, only the front moves, in O(1); otherwise, it is
+    /// O(min(n, len - n)), with each step moving one value from one end to the other.
+    ///
+    /// # Panics
+    ///
+    /// If `n > len`.
+    #[track_caller]
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.len();
+        assert!(
+            n <= len,
+            "rotation amount (is {n}) should be <= len (is {len})"
+        );
+        if self.rotate_full_left(len - n) {
+            // already rotated
+        } else if n <= len - n {
+            self.rotate_back_to_front(n);
+        } else {
+            self.rotate_front_to_back(len - n);
+        }
+    }
+
+    /// Move `n <= len` values from the front to the back, one at a time.
+    fn rotate_front_to_back(&mut self, n: usize) {
+        for _ in 0..n {
+            // there is room again right after popping, so pushing never grows
+            if let Some(value) = self.pop_front() {
+                self.push_back(value);
+            }
+        }
+    }
+
+    /// Move `n <= len` values from the back to the front, one at a time.
+    fn rotate_back_to_front(&mut self, n: usize) {
+        for _ in 0..n {
+            // there is room again right after popping, so pushing never grows
+            if let Some(value) = self.pop_back() {
+                self.push_front(value);
+            }
+        }
+    }
+}
+
+impl<T> MyDeque<T> {
+    /// Iterate over references to the values, from front to back (or back to front, with [`rev`](Iterator::rev)).
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    /// Iterate over mutable references to the values, from front to back (or back to front, with [`rev`](Iterator::rev)).
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+
+    /// Push every value from an iterator onto the front of the deque, keeping the iterator's order.
+    ///
+    /// The values are pushed from the back of the iterator, so that its first value ends up at the front of the deque.
+    pub fn extend_front<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<IntoIter: DoubleEndedIterator<Item = T>>,
+    {
+        iter.into_iter()
+            .rev()
+            .for_each(|value| self.push_front(value));
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T> Default for MyDeque<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translation of `operator[]`, counting from the front.
+///
+/// # Panics
+///
+/// If `index >= len`.
+impl<T> Index<usize> for MyDeque<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+/// Translation of `operator[]`, counting from the front.
+///
+/// # Panics
+///
+/// If `index >= len`.
+impl<T> IndexMut<usize> for MyDeque<T> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+///
+/// The clone is compact: its values start at the beginning of its buffer (so they never wrap), with no spare capacity.
+impl<T: Clone> Clone for MyDeque<T> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::with_capacity(self.len());
+        clone.extend(self.iter().cloned());
+        clone
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way, from front to back.
+impl<T: fmt::Debug> fmt::Debug for MyDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Translation of `operator==`: deques are equal if they hold equal values in the same order,
+/// no matter where in their buffers the values are.
+impl<T: PartialEq> PartialEq for MyDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for MyDeque<T> {}
+
+/// Rust-specific helper to hash deques by their values, consistent with [`PartialEq`].
+///
+/// The length is hashed first, so that nested deques like `[[1], [2]]` and `[[1, 2]]` hash differently.
+impl<T: Hash> Hash for MyDeque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|value| value.hash(state));
+    }
+}
+
+/// Iterator over references to the values of a [`MyDeque`], created by [`MyDeque::iter`].
+pub struct Iter<'a, T> {
+    // the part from the front to the end of the buffer, then the part wrapped around to its start
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Translation of the copy constructor into Rust.
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&DebugValues(
+                self.front.as_slice().iter().chain(self.back.as_slice()),
+            ))
+            .finish()
+    }
+}
+
+/// Iterator over mutable references to the values of a [`MyDeque`], created by [`MyDeque::iter_mut`].
+pub struct IterMut<'a, T> {
+    // the part from the front to the end of the buffer, then the part wrapped around to its start
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IterMut")
+            .field(&DebugValues(
+                self.front.as_slice().iter().chain(self.back.as_slice()),
+            ))
+            .finish()
+    }
+}
+
+/// Owning iterator over the values of a [`MyDeque`], created by [`MyDeque::into_iter`](IntoIterator::into_iter).
+///
+/// Any values that weren't yielded (in either part of a wrapped-around deque) are dropped, and the buffer is freed,
+/// when the iterator is dropped.
+pub struct IntoIter<T> {
+    deque: MyDeque<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len(), Some(self.deque.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.deque).finish()
+    }
+}
+
+/// Rust-specific helper to move the values out of a deque, from front to back (or back to front, with [`rev`](Iterator::rev)).
+impl<T> IntoIterator for MyDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { deque: self }
+    }
+}
+
+/// Rust-specific helper to iterate over a deque by reference, as in `for value in &deque`.
+impl<'a, T> IntoIterator for &'a MyDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Rust-specific helper to iterate over a deque by mutable reference, as in `for value in &mut deque`.
+impl<'a, T> IntoIterator for &'a mut MyDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Rust-specific helper to allow collecting an iterator into a deque, keeping the iterator's order.
+impl<T> FromIterator<T> for MyDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut deque = Self::with_capacity(iter.size_hint().0);
+        deque.extend(iter);
+        deque
+    }
+}
+
+/// Rust-specific helper to push every value from an iterator onto the back of the deque, keeping the iterator's order.
+///
+/// See [`MyDeque::extend_front`] to push them onto the front instead.
+impl<T> Extend<T> for MyDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push_back(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_drop::CountDrops, test_rng::TestRng, test_std::to_std};
+
+    #[test]
+    fn push_pop_both_ends() {
+        let mut deque = MyDeque::new();
+        assert_eq!(
+            (deque.capacity(), deque.front(), deque.back()),
+            (0, None, None)
+        );
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        deque.push_front(0);
+        assert_eq!(to_std(&deque), [0, 1, 2, 3]);
+        assert_eq!((deque.front(), deque.back()), (Some(&0), Some(&3)));
+        *deque.front_mut().unwrap() = 10;
+        *deque.back_mut().unwrap() = 30;
+        deque[1] = 11;
+        assert_eq!(std::format!("{deque:?}"), "[10, 11, 2, 30]");
+        assert_eq!((deque.pop_front(), deque.pop_back()), (Some(10), Some(30)));
+        assert_eq!(
+            (deque.pop_back(), deque.pop_back(), deque.pop_back()),
+            (Some(2), Some(11), None)
+        );
+        assert_eq!(deque.pop_front(), None);
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn interleaved_against_std() {
+        let mut deque = MyDeque::with_capacity(3);
+        let mut expected = std::collections::VecDeque::new();
+        // pushes outnumber pops, so the deque keeps growing through a small starting capacity while it wraps around
+        let mut rng = TestRng::new(7);
+        for i in 0..2_000 {
+            match rng.below(5) {
+                0 | 1 => {
+                    deque.push_back(i);
+                    expected.push_back(i);
+                }
+                2 => {
+                    deque.push_front(i);
+                    expected.push_front(i);
+                }
+                3 => assert_eq!(deque.pop_front(), expected.pop_front()),
+                _ => assert_eq!(deque.pop_back(), expected.pop_back()),
+            }
+            assert_eq!(deque.len(), expected.len());
+            assert!(deque.len() <= deque.capacity());
+            if i % 97 == 0 {
+                assert!(to_std(&deque).iter().eq(&expected));
+            }
+        }
+        assert!(to_std(&deque).iter().eq(&expected));
+    }
+
+    #[test]
+    fn grow_while_wrapped() {
+        // the back is shorter: it moves after the old end
+        let mut deque = MyDeque::with_capacity(4);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_front(0);
+        deque.push_front(-1);
+        assert_eq!(deque.capacity(), 4);
+        deque.push_back(3);
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(to_std(&deque), [-1, 0, 1, 2, 3]);
+
+        // the front is shorter: it moves to the new end
+        let mut deque = MyDeque::with_capacity(4);
+        for i in 1..=3 {
+            deque.push_back(i);
+        }
+        deque.push_front(0);
+        deque.push_front(-1);
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(to_std(&deque), [-1, 0, 1, 2, 3]);
+        for i in 4..=6 {
+            deque.push_back(i);
+        }
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(to_std(&deque), [-1, 0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn drops_each_value_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut deque = MyDeque::with_capacity(4);
+        for i in 0..3 {
+            deque.push_front(CountDrops::new(i, &drops));
+            deque.push_back(CountDrops::new(i, &drops));
+        }
+        assert_eq!(deque.pop_front().map(|value| value.value), Some(2));
+        assert_eq!(drops.get(), 1);
+        drop(deque);
+        assert_eq!(drops.get(), 6);
+
+        // wrapped around, with both parts non-empty
+        let mut deque = MyDeque::with_capacity(4);
+        deque.push_back(CountDrops::new(1, &drops));
+        deque.push_front(CountDrops::new(0, &drops));
+        deque.clear();
+        assert_eq!(drops.get(), 8);
+        assert_eq!((deque.len(), deque.capacity()), (0, 4));
+    }
+
+    #[test]
+    fn zst_never_allocates() {
+        use crate::test_alloc::count_allocations;
+
+        let ((), allocations) = count_allocations(|| {
+            let mut deque = MyDeque::new();
+            for _ in 0..10 {
+                deque.push_front(());
+                deque.push_back(());
+            }
+            assert_eq!((deque.len(), deque.capacity()), (20, usize::MAX));
+            assert_eq!(deque.pop_back(), Some(()));
+            assert_eq!(deque.get(18), Some(&()));
+            assert_eq!(deque.get(19), None);
+        });
+        assert_eq!(allocations, 0);
+    }
+
+    /// Build a deque with the values `0..len` at capacity `cap`, starting at physical slot `head`.
+    fn deque_at(cap: usize, head: usize, len: usize) -> MyDeque<usize> {
+        let mut deque = MyDeque::with_capacity(cap);
+        // pushing and popping `head` values moves the front along without growing
+        for _ in 0..head {
+            deque.push_back(0);
+            deque.pop_front();
+        }
+        (0..len).for_each(|value| deque.push_back(value));
+        assert_eq!(deque.capacity(), cap);
+        deque
+    }
+
+    #[test]
+    fn make_contiguous_every_layout() {
+        use crate::test_alloc::count_allocations;
+
+        let cap = 8;
+        for head in 0..cap {
+            for len in 0..=cap {
+                let mut deque = deque_at(cap, head, len);
+                let (front, back) = deque.as_slices();
+                assert_eq!(front.len() + back.len(), len);
+                assert!(front.iter().chain(back).copied().eq(0..len));
+                let (front, back) = deque.as_mut_slices();
+                front.iter_mut().chain(back).for_each(|value| *value *= 10);
+
+                let (contiguous_len, allocations) =
+                    count_allocations(|| deque.make_contiguous().len());
+                assert_eq!((contiguous_len, allocations), (len, 0));
+                let (front, back) = deque.as_slices();
+                assert!(front.iter().copied().eq((0..len).map(|value| value * 10)));
+                assert!(back.is_empty());
+                assert_eq!(to_std(&deque).len(), len);
+                // the deque keeps working afterwards
+                deque.push_front(1);
+                deque.push_back(2);
+                assert_eq!((deque.front(), deque.back()), (Some(&1), Some(&2)));
+            }
+        }
+    }
+
+    #[test]
+    fn rotate_against_std() {
+        let mut deque = deque_at(16, 11, 13);
+        let mut expected: std::vec::Vec<usize> = (0..13).collect();
+        // rotations by every amount up to `len`, mixed with pushes that move where the buffer wraps
+        let mut rng = TestRng::new(11);
+        for _ in 0..500 {
+            let n = rng.below(expected.len() + 1);
+            match rng.below(4) {
+                0 => {
+                    deque.rotate_left(n);
+                    expected.rotate_left(n);
+                }
+                1 => {
+                    deque.rotate_right(n);
+                    expected.rotate_right(n);
+                }
+                2 => {
+                    deque.make_contiguous();
+                }
+                _ => {
+                    deque.push_back(n);
+                    expected.push(n);
+                    if expected.len() > 20 {
+                        deque.pop_front();
+                        expected.remove(0);
+                    }
+                }
+            }
+            let (front, back) = deque.as_slices();
+            assert!(front.iter().chain(back).eq(&expected));
+        }
+
+        // rotating by 0 or `len` doesn't change anything, even on a contiguous deque
+        let mut deque = deque_at(4, 0, 4);
+        deque.rotate_left(0);
+        deque.rotate_right(4);
+        assert_eq!(deque.as_slices(), (&[0, 1, 2, 3][..], &[][..]));
+    }
+
+    #[test]
+    fn rotate_full_deque() {
+        // every amount, from every starting slot, in a buffer with no free slot
+        for head in 0..7 {
+            for n in 0..=7 {
+                let mut deque = deque_at(7, head, 7);
+                let mut expected: std::vec::Vec<usize> = (0..7).collect();
+                deque.rotate_left(n);
+                expected.rotate_left(n);
+                assert!(deque.iter().eq(&expected));
+                deque.rotate_right(n.saturating_sub(2));
+                expected.rotate_right(n.saturating_sub(2));
+                assert!(deque.iter().eq(&expected));
+                assert_eq!(deque.capacity(), 7);
+            }
+        }
+
+        // the values stay where they are: only the front moves
+        let mut deque = deque_at(4, 1, 4);
+        let slots = deque.as_slices().0.as_ptr();
+        deque.rotate_left(2);
+        assert_eq!(deque.as_slices(), (&[2][..], &[3, 0, 1][..]));
+        assert_eq!(deque.as_slices().1.as_ptr(), slots.wrapping_sub(1));
+    }
+
+    #[test]
+    #[should_panic = "rotation amount (is 3) should be <= len (is 2)"]
+    fn rotate_too_far() {
+        deque_at(4, 3, 2).rotate_left(3);
+    }
+
+    #[test]
+    fn iterate_across_the_wrap() {
+        let mut deque = deque_at(8, 5, 7);
+        assert!(!deque.as_slices().1.is_empty());
+        assert!(deque.iter().copied().eq(0..7));
+        assert!(deque.iter().rev().copied().eq((0..7).rev()));
+        for value in &mut deque {
+            *value *= 2;
+        }
+        assert!(
+            (&deque)
+                .into_iter()
+                .copied()
+                .eq((0..7).map(|value| value * 2))
+        );
+
+        // take turns from the front and back, until both sides meet
+        for len in [6, 7] {
+            let deque = deque_at(8, 5, len);
+            let expected: std::vec::Vec<usize> = (0..len).collect();
+            let mut iter = deque.iter();
+            let mut iter_mut_deque = deque.clone();
+            let mut iter_mut = iter_mut_deque.iter_mut();
+            let mut into_iter = deque.clone().into_iter();
+            let mut expected_iter = expected.iter();
+            for step in 0.. {
+                assert_eq!(iter.len(), expected_iter.len());
+                assert_eq!(iter_mut.len(), expected_iter.len());
+                assert_eq!(into_iter.len(), expected_iter.len());
+                let (value, value_mut, owned, expected_value) = if step % 2 == 0 {
+                    (
+                        iter.next(),
+                        iter_mut.next(),
+                        into_iter.next(),
+                        expected_iter.next(),
+                    )
+                } else {
+                    (
+                        iter.next_back(),
+                        iter_mut.next_back(),
+                        into_iter.next_back(),
+                        expected_iter.next_back(),
+                    )
+                };
+                assert_eq!(value, expected_value);
+                assert_eq!(value_mut.as_deref(), expected_value);
+                assert_eq!(owned.as_ref(), expected_value);
+                if value.is_none() {
+                    break;
+                }
+            }
+            assert_eq!((iter.next(), iter.next_back()), (None, None));
+        }
+    }
+
+    #[test]
+    fn clone_compare_and_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let wrapped = deque_at(8, 6, 5);
+        let clone = wrapped.clone();
+        assert_eq!(clone, wrapped);
+        assert_eq!(clone.capacity(), 5);
+        assert!(clone.as_slices().1.is_empty());
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&clone), state.hash_one(&wrapped));
+
+        let collected: MyDeque<usize> = (0..5).collect();
+        assert_eq!(collected, wrapped);
+        let mut longer = collected.clone();
+        longer.push_back(5);
+        assert_ne!(longer, wrapped);
+
+        let mut iter = wrapped.iter();
+        iter.next();
+        assert_eq!(std::format!("{iter:?}"), "Iter([1, 2, 3, 4])");
+        let mut clone = clone;
+        let mut iter_mut = clone.iter_mut();
+        iter_mut.next_back();
+        assert_eq!(std::format!("{iter_mut:?}"), "IterMut([0, 1, 2, 3])");
+        assert_eq!(
+            std::format!("{:?}", clone.into_iter()),
+            "IntoIter([0, 1, 2, 3, 4])"
+        );
+    }
+
+    #[test]
+    fn extend_both_ends() {
+        let mut deque: MyDeque<_> = [3, 4].into_iter().collect();
+        deque.extend([5, 6]);
+        deque.extend_front([0, 1, 2]);
+        assert_eq!(to_std(&deque), [0, 1, 2, 3, 4, 5, 6]);
+        deque.extend_front(core::iter::empty());
+        assert_eq!(deque.len(), 7);
+    }
+
+    #[test]
+    fn into_iter_drops_rest() {
+        let drops = core::cell::Cell::new(0);
+        let mut deque = MyDeque::with_capacity(8);
+        for i in 0..4 {
+            deque.push_back(CountDrops::new(i, &drops));
+        }
+        for i in 0..3 {
+            deque.push_front(CountDrops::new(10 + i, &drops));
+        }
+        // wrapped around, with values in both parts
+        assert!(!deque.as_slices().1.is_empty());
+        let mut iter = deque.into_iter();
+        assert_eq!(iter.next().map(|value| value.value), Some(12));
+        assert_eq!(iter.next_back().map(|value| value.value), Some(3));
+        assert_eq!(drops.get(), 2);
+        drop(iter);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    #[should_panic = "index out of bounds: the len is 2 but the index is 2"]
+    fn index_out_of_bounds() {
+        let mut deque = MyDeque::new();
+        deque.push_back(1);
+        deque.push_front(0);
+        let _ = deque[2];
+    }
+}
@@ -0,0 +1,421 @@
+//! First-in, first-out queue data structure.
+//!
+//! Every value lives in its own heap node, linked to the node after it, and the queue keeps pointers to both ends,
+//! so values can be added at the back and removed from the front in O(1).
+
+use crate::debug_values::DebugValues;
+use core::{fmt, iter::FusedIterator};
+
+pub use implementation::{Iter, MyQueue};
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use crate::my_box::MyBox;
+    use core::{marker::PhantomData, ptr::NonNull};
+
+    /// Non-owning link to the next node, if any.
+    type Link<T> = Option<NonNull<Node<T>>>;
+
+    /// A single heap node in the queue, allocated by a [`MyBox`].
+    struct Node<T> {
+        value: T,
+        next: Link<T>,
+    }
+
+    impl<T> Node<T> {
+        /// Allocate a node for a value about to be enqueued, with nothing behind it yet.
+        fn new(value: T) -> NonNull<Self> {
+            NonNull::from(MyBox::new(Self { value, next: None }).leak())
+        }
+
+        /// Reclaim a node that has been taken off the front of the queue, returning its value.
+        ///
+        /// # Safety
+        ///
+        /// `node` must have come from [`Node::new`], and nothing may point to it anymore.
+        unsafe fn into_value(node: NonNull<Self>) -> T {
+            // SAFETY: `node` was leaked from a `MyBox` by `Node::new`, and the caller guarantees it is no longer linked
+            unsafe { MyBox::from_raw(node.as_ptr()) }.into_inner().value
+        }
+    }
+
+    /// Linked first-in, first-out queue of values.
+    ///
+    /// Unlike [`MyDeque`](crate::my_deque::MyDeque), which is a ring buffer, every value is in its own heap node.
+    pub struct MyQueue<T> {
+        /*
+        invariants:
+        - `head` and `tail` are both `None` (and `len == 0`), or both point to nodes owned by this queue
+        - following `next` from `head` visits exactly `len` nodes, and ends at `tail`, whose `next` is `None`
+        */
+        head: Link<T>,
+        tail: Link<T>,
+        len: usize,
+        // tells the drop checker that the queue owns its nodes (and so their values)
+        _owns: PhantomData<MyBox<Node<T>>>,
+    }
+
+    impl<T> MyQueue<T> {
+        /// Create an empty queue, without allocating.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                head: None,
+                tail: None,
+                len: 0,
+                _owns: PhantomData,
+            }
+        }
+
+        /// Number of values in the queue, in O(1).
+        #[doc(alias = "size")]
+        #[inline]
+        pub const fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Add `value` to the back of the queue.
+        #[doc(alias("push", "push_back"))]
+        pub fn enqueue(&mut self, value: T) {
+            let node = Node::new(value);
+            match self.tail {
+                // SAFETY: `tail` is owned by this queue, and the queue is mutably borrowed
+                Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+                None => self.head = Some(node),
+            }
+            self.tail = Some(node);
+            self.len += 1;
+        }
+
+        /// Remove the value at the front of the queue and return it, or `None` if it is empty.
+        #[doc(alias("pop", "pop_front"))]
+        pub fn dequeue(&mut self) -> Option<T> {
+            let head = self.head?;
+            // SAFETY: `head` is owned by this queue, and is unlinked before it is freed
+            unsafe {
+                self.head = (*head.as_ptr()).next;
+                if self.head.is_none() {
+                    // that was the last node, so the tail must not keep pointing at it
+                    self.tail = None;
+                }
+                self.len -= 1;
+                Some(Node::into_value(head))
+            }
+        }
+
+        /// Get a reference to the value at the front of the queue, or `None` if it is empty.
+        #[doc(alias = "front")]
+        #[inline]
+        pub fn peek(&self) -> Option<&T> {
+            // SAFETY: `head` is owned by this queue, and the reference borrows the queue
+            self.head.map(|head| unsafe { &(*head.as_ptr()).value })
+        }
+
+        /// Get a mutable reference to the value at the front of the queue, or `None` if it is empty.
+        #[inline]
+        pub fn peek_mut(&mut self) -> Option<&mut T> {
+            // SAFETY: `head` is owned by this queue, and the reference mutably borrows the queue
+            self.head.map(|head| unsafe { &mut (*head.as_ptr()).value })
+        }
+
+        /// Iterate over references to the values, from front to back.
+        #[inline]
+        pub fn iter(&self) -> Iter<'_, T> {
+            Iter {
+                next: self.head,
+                len: self.len,
+                _borrows: PhantomData,
+            }
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Nodes are freed one at a time, so even very long queues can't overflow the stack.
+    impl<T> Drop for MyQueue<T> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    // SAFETY: the queue uniquely owns its nodes (like `MyBox<Node<T>>`s), so it is as thread-safe as `T`
+    unsafe impl<T: Send> Send for MyQueue<T> {}
+
+    // SAFETY: `&MyQueue` only gives out `&T`, so sharing the queue is equivalent to sharing the values
+    unsafe impl<T: Sync> Sync for MyQueue<T> {}
+
+    /// Iterator over references to the values of a [`MyQueue`], created by [`MyQueue::iter`].
+    pub struct Iter<'a, T> {
+        // invariant: `next` starts the `len` nodes that haven't been yielded yet, which are borrowed from the queue
+        next: Link<T>,
+        len: usize,
+        _borrows: PhantomData<&'a T>,
+    }
+
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<&'a T> {
+            let node = self.next?;
+            // SAFETY: the node hasn't been yielded, and is borrowed from the queue for `'a`
+            let node = unsafe { &*node.as_ptr() };
+            self.next = node.next;
+            self.len -= 1;
+            Some(&node.value)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
+        }
+    }
+
+    /// Translation of the copy constructor into Rust.
+    impl<T> Clone for Iter<'_, T> {
+        fn clone(&self) -> Self {
+            Self { ..*self }
+        }
+    }
+
+    // SAFETY: the iterator only gives out `&T`, just like `&MyQueue<T>`
+    unsafe impl<T: Sync> Send for Iter<'_, T> {}
+
+    // SAFETY: the iterator only gives out `&T`, just like `&MyQueue<T>`
+    unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+}
+
+impl<T> MyQueue<T> {
+    /// Check whether the queue has no values.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every value in the queue, freeing every node.
+    ///
+    /// Values are dequeued one at a time, so if a destructor panics, the values behind it are still in the queue.
+    pub fn clear(&mut self) {
+        while self.dequeue().is_some() {}
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T> Default for MyQueue<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+impl<T: Clone> Clone for MyQueue<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way, from front to back.
+impl<T: fmt::Debug> fmt::Debug for MyQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&DebugValues(self.clone()))
+            .finish()
+    }
+}
+
+/// Owning iterator over the values of a [`MyQueue`], created by [`MyQueue::into_iter`](IntoIterator::into_iter).
+///
+/// Values are dequeued as they are yielded, and any values that weren't yielded are dropped (along with their nodes)
+/// when the iterator is dropped.
+pub struct IntoIter<T> {
+    queue: MyQueue<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.queue.dequeue()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.queue).finish()
+    }
+}
+
+/// Rust-specific helper to dequeue every value, from front to back.
+impl<T> IntoIterator for MyQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { queue: self }
+    }
+}
+
+/// Rust-specific helper to iterate over a queue by reference, as in `for value in &queue`.
+impl<'a, T> IntoIterator for &'a MyQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Rust-specific helper to allow collecting an iterator into a queue, keeping the iterator's order.
+impl<T> FromIterator<T> for MyQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+/// Rust-specific helper to enqueue every value from an iterator, keeping the iterator's order.
+impl<T> Extend<T> for MyQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.enqueue(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_drop::CountDrops;
+
+    #[test]
+    fn first_in_first_out() {
+        let mut queue = MyQueue::new();
+        assert_eq!(queue.peek(), None);
+        assert_eq!(queue.dequeue(), None);
+        queue.enqueue(1);
+        queue.enqueue(2);
+        assert_eq!(queue.peek(), Some(&1));
+        *queue.peek_mut().unwrap() = 10;
+        queue.enqueue(3);
+        assert_eq!(std::format!("{queue:?}"), "[10, 2, 3]");
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.dequeue(), Some(10));
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.dequeue(), Some(3));
+        assert_eq!(queue.dequeue(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn drain_and_refill() {
+        let mut queue = MyQueue::new();
+        for round in 0..5 {
+            // the tail has to be reset when the last value leaves, or the next enqueue would link onto a freed node
+            for i in 0..round {
+                queue.enqueue(round * 10 + i);
+            }
+            assert_eq!(queue.len(), round);
+            for i in 0..round {
+                assert_eq!(queue.dequeue(), Some(round * 10 + i));
+            }
+            assert_eq!((queue.dequeue(), queue.peek()), (None, None));
+            queue.enqueue(100);
+            assert_eq!(queue.dequeue(), Some(100));
+        }
+    }
+
+    #[test]
+    fn iterate_collect_and_extend() {
+        let mut queue: MyQueue<_> = (1..=3).collect();
+        queue.extend([4, 5]);
+        assert_eq!(queue.iter().sum::<i32>(), 15);
+        let mut iter = queue.iter();
+        iter.next();
+        assert_eq!(
+            (iter.len(), std::format!("{iter:?}")),
+            (4, "Iter([2, 3, 4, 5])".into())
+        );
+
+        let clone = queue.clone();
+        queue.dequeue();
+        let mut into_iter = queue.into_iter();
+        assert_eq!(into_iter.next(), Some(2));
+        assert_eq!(std::format!("{into_iter:?}"), "IntoIter([3, 4, 5])");
+        assert!(into_iter.eq(3..=5));
+        assert!((&clone).into_iter().copied().eq(1..=5));
+    }
+
+    #[test]
+    fn drops_each_value_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut queue: MyQueue<_> = (0..5).map(|i| CountDrops::new(i, &drops)).collect();
+        assert_eq!(queue.dequeue().map(|value| value.value), Some(0));
+        assert_eq!(drops.get(), 1);
+        queue.clear();
+        assert_eq!((drops.get(), queue.len()), (5, 0));
+
+        queue.extend((0..4).map(|i| CountDrops::new(i, &drops)));
+        let mut iter = queue.into_iter();
+        iter.next();
+        drop(iter);
+        assert_eq!(drops.get(), 9);
+    }
+
+    #[test]
+    fn clear_with_panicking_destructor() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        struct PanicOnDrop(bool);
+
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                assert!(!self.0, "destructor panicked");
+            }
+        }
+
+        let mut queue: MyQueue<_> = [false, true, false, false]
+            .map(PanicOnDrop)
+            .into_iter()
+            .collect();
+        assert!(catch_unwind(AssertUnwindSafe(|| queue.clear())).is_err());
+        // the values behind the panicking one were never reached
+        assert_eq!(queue.len(), 2);
+        queue.clear();
+        assert!(queue.is_empty() && queue.peek().is_none());
+    }
+
+    #[test]
+    fn long_queue_drop() {
+        // enough nodes that dropping them recursively would overflow the stack
+        let len = if cfg!(miri) { 1_000 } else { 200_000 };
+        let drops = core::cell::Cell::new(0);
+        let queue: MyQueue<_> = (0..len).map(|i| CountDrops::new(i, &drops)).collect();
+        assert_eq!(queue.len(), len);
+        drop(queue);
+        assert_eq!(drops.get(), len);
+    }
+}
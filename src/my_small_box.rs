@@ -0,0 +1,264 @@
+//! Small-value-optimized variant of [`MyBox`].
+//!
+//! Small values are stored inline, right inside the box, and only values too big (or too strictly aligned) for the inline
+//! buffer are moved to the heap. This avoids an allocation for tiny payloads, while keeping the same interface as `MyBox`.
+
+use crate::my_box::MyBox;
+use core::fmt;
+
+pub use implementation::MySmallBox;
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use crate::my_box::MyBox;
+    use core::{
+        marker::PhantomData,
+        mem::{self, ManuallyDrop, MaybeUninit},
+        ops::{Deref, DerefMut},
+    };
+
+    /// Inline buffer of `N` bytes, aligned like a `usize`.
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct InlineBuffer<const N: usize> {
+        _align: [usize; 0],
+        bytes: [MaybeUninit<u8>; N],
+    }
+
+    /// Storage for the value, in whichever representation was chosen for `T`.
+    union Storage<T, const N: usize> {
+        inline: InlineBuffer<N>,
+        heap: ManuallyDrop<MyBox<T>>,
+    }
+
+    /// Single value, stored inline if it fits in `N` bytes, or on the heap otherwise.
+    ///
+    /// The representation only depends on `T` and `N`, so it is decided at compile time (see [`INLINE`](Self::INLINE)).
+    pub struct MySmallBox<T, const N: usize> {
+        // invariant: if `INLINE`, `storage.inline` holds a valid `T`; otherwise `storage.heap` holds a valid `MyBox<T>`
+        storage: Storage<T, N>,
+        // tells the drop checker that a `MySmallBox<T, N>` owns a `T`, even when it is stored inline
+        _owns: PhantomData<T>,
+    }
+
+    impl<T, const N: usize> MySmallBox<T, N> {
+        /// Whether values of `T` are stored inline (`true`), or on the heap (`false`).
+        ///
+        /// Values are stored inline if they fit in `N` bytes, and need no more alignment than a `usize`.
+        pub const INLINE: bool =
+            mem::size_of::<T>() <= N && mem::align_of::<T>() <= mem::align_of::<InlineBuffer<N>>();
+
+        /// Store the given `value`, inline if it fits, or on the heap otherwise.
+        #[inline]
+        pub fn new(value: T) -> Self {
+            let storage = if Self::INLINE {
+                let mut inline = InlineBuffer {
+                    _align: [],
+                    bytes: [MaybeUninit::uninit(); N],
+                };
+                // SAFETY: `T` fits in the buffer, which is aligned enough for it (checked by `INLINE`)
+                unsafe { inline.bytes.as_mut_ptr().cast::<T>().write(value) };
+                Storage { inline }
+            } else {
+                Storage {
+                    heap: ManuallyDrop::new(MyBox::new(value)),
+                }
+            };
+            Self {
+                storage,
+                _owns: PhantomData,
+            }
+        }
+
+        /// Move the value out and return it, deallocating it if it was on the heap.
+        #[inline]
+        pub fn into_inner(self) -> T {
+            // ensure that `self` is never dropped, since the value moves to the caller
+            let mut this = ManuallyDrop::new(self);
+            if Self::INLINE {
+                // SAFETY: the inline buffer holds a valid `T`, which is never accessed again (the box is never dropped)
+                unsafe { this.storage.inline.bytes.as_ptr().cast::<T>().read() }
+            } else {
+                // SAFETY: the heap box is valid, and is never accessed again (the box is never dropped)
+                unsafe { ManuallyDrop::take(&mut this.storage.heap) }.into_inner()
+            }
+        }
+    }
+
+    /// Translation of requirement to be able to read the value.
+    impl<T, const N: usize> Deref for MySmallBox<T, N> {
+        type Target = T;
+
+        #[doc(alias = "read")]
+        #[inline]
+        fn deref(&self) -> &T {
+            if Self::INLINE {
+                // SAFETY: the inline buffer holds a valid `T`
+                unsafe { &*self.storage.inline.bytes.as_ptr().cast::<T>() }
+            } else {
+                // SAFETY: the heap box is valid
+                unsafe { &self.storage.heap }
+            }
+        }
+    }
+
+    /// Translation of requirement to be able to overwrite the value.
+    impl<T, const N: usize> DerefMut for MySmallBox<T, N> {
+        #[doc(alias = "write")]
+        #[inline]
+        fn deref_mut(&mut self) -> &mut T {
+            if Self::INLINE {
+                // SAFETY: the inline buffer holds a valid `T`, and the caller has an exclusive reference to the box
+                unsafe { &mut *self.storage.inline.bytes.as_mut_ptr().cast::<T>() }
+            } else {
+                // SAFETY: the heap box is valid, and the caller has an exclusive reference to the box
+                unsafe { &mut self.storage.heap }
+            }
+        }
+    }
+
+    /// Translation of destructor.
+    impl<T, const N: usize> Drop for MySmallBox<T, N> {
+        #[inline]
+        fn drop(&mut self) {
+            if Self::INLINE {
+                // SAFETY: the inline buffer holds a valid `T`, and `drop` is the last function to ever be called on `self`
+                unsafe {
+                    self.storage
+                        .inline
+                        .bytes
+                        .as_mut_ptr()
+                        .cast::<T>()
+                        .drop_in_place()
+                };
+            } else {
+                // SAFETY: the heap box is valid, and `drop` is the last function to ever be called on `self`
+                unsafe { ManuallyDrop::drop(&mut self.storage.heap) };
+            }
+        }
+    }
+}
+
+/// Rust-specific helper to visualize this type in a programmer-friendly way, including where the value is stored.
+impl<T: fmt::Debug, const N: usize> fmt::Debug for MySmallBox<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if Self::INLINE {
+            "MySmallBox::Inline"
+        } else {
+            "MySmallBox::Heap"
+        };
+        f.debug_tuple(name).field(&**self).finish()
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+impl<T: Clone, const N: usize> Clone for MySmallBox<T, N> {
+    fn clone(&self) -> Self {
+        Self::new(T::clone(self))
+    }
+
+    /// Clone `source`'s contents into the existing storage, instead of making a new box.
+    fn clone_from(&mut self, source: &Self) {
+        T::clone_from(self, source);
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T: Default, const N: usize> Default for MySmallBox<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Rust-specific helper to move a heap box into a small box (which may move the value back inline).
+impl<T, const N: usize> From<MyBox<T>> for MySmallBox<T, N> {
+    #[inline]
+    fn from(boxed: MyBox<T>) -> Self {
+        Self::new(boxed.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_alloc::count_allocations, test_drop::CountDrops};
+
+    #[test]
+    fn small_value_is_inline() {
+        let (mut boxed, allocations) = count_allocations(|| MySmallBox::<u32, 8>::new(5));
+        const { assert!(MySmallBox::<u32, 8>::INLINE) };
+        assert_eq!(allocations, 0);
+        *boxed += 1;
+        assert_eq!(*boxed, 6);
+        assert_eq!(std::format!("{boxed:?}"), "MySmallBox::Inline(6)");
+        assert_eq!(boxed.into_inner(), 6);
+    }
+
+    #[test]
+    fn large_value_spills() {
+        let (boxed, allocations) =
+            count_allocations(|| MySmallBox::<[u8; 1024], 16>::new([7; 1024]));
+        const { assert!(!MySmallBox::<[u8; 1024], 16>::INLINE) };
+        assert_eq!(allocations, 1);
+        assert!(boxed.iter().all(|&byte| byte == 7));
+        assert!(std::format!("{boxed:?}").starts_with("MySmallBox::Heap(["));
+
+        let (cloned, allocations) = count_allocations(|| boxed.clone());
+        assert_eq!(allocations, 1);
+        assert_eq!(*cloned, *boxed);
+        assert_eq!(cloned.into_inner(), [7; 1024]);
+    }
+
+    #[test]
+    fn overaligned_value_spills() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[repr(align(64))]
+        struct Aligned(u8);
+
+        const { assert!(!MySmallBox::<Aligned, 128>::INLINE) };
+        let boxed = MySmallBox::<Aligned, 128>::new(Aligned(3));
+        assert_eq!((&raw const *boxed).addr() % 64, 0);
+        assert_eq!(*boxed, Aligned(3));
+    }
+
+    #[test]
+    fn zst_is_inline() {
+        const { assert!(MySmallBox::<(), 0>::INLINE) };
+        let (boxed, allocations) = count_allocations(|| MySmallBox::<(), 0>::new(()));
+        assert_eq!(allocations, 0);
+        assert_eq!(*boxed, ());
+        assert_eq!(boxed.into_inner(), ());
+    }
+
+    #[test]
+    fn drops_once_inline_and_heap() {
+        let drops = core::cell::Cell::new(0);
+
+        let inline = MySmallBox::<_, 16>::new(CountDrops::new(1, &drops));
+        const { assert!(MySmallBox::<CountDrops<'_, i32>, 16>::INLINE) };
+        let heap = MySmallBox::<_, 4>::new(CountDrops::new(2, &drops));
+        const { assert!(!MySmallBox::<CountDrops<'_, i32>, 4>::INLINE) };
+        assert_eq!((inline.value, heap.value), (1, 2));
+        assert_eq!(drops.get(), 0);
+
+        core::mem::drop((inline, heap));
+        assert_eq!(drops.get(), 2);
+
+        // moving the value out hands over responsibility for dropping it
+        let inline = MySmallBox::<_, 16>::new(CountDrops::new(3, &drops)).into_inner();
+        let heap = MySmallBox::<_, 4>::new(CountDrops::new(4, &drops)).into_inner();
+        assert_eq!(drops.get(), 2);
+        core::mem::drop((inline, heap));
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn from_my_box_moves_inline() {
+        let (boxed, allocations) = count_allocations(|| MySmallBox::<u64, 8>::from(MyBox::new(9)));
+        assert_eq!(allocations, 1);
+        assert_eq!(*boxed, 9);
+        assert_eq!(std::format!("{boxed:?}"), "MySmallBox::Inline(9)");
+    }
+}
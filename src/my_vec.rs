@@ -0,0 +1,1829 @@
+//! Growable array data structure.
+//!
+//! This data structure was originally called "MyVector" in the course.
+//! Elements are stored contiguously on the heap, and the buffer doubles in size whenever it runs out of room,
+//! so that pushing is amortized O(1).
+
+use core::{
+    fmt,
+    iter::FusedIterator,
+    ops::{
+        Bound, Deref, DerefMut, Index, IndexMut, Range, RangeBounds, RangeFrom, RangeFull,
+        RangeInclusive, RangeTo, RangeToInclusive,
+    },
+};
+
+pub use implementation::{Drain, IntoIter, MyVec, Splice};
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use crate::{allocator::TryReserveError, raw_buf::RawBuf};
+    use core::{
+        mem::ManuallyDrop,
+        ops::{Range, RangeBounds},
+        ptr, slice,
+    };
+
+    /// Growable array of values, stored contiguously on the heap.
+    ///
+    /// Like [`alloc::vec::Vec`], zero-sized values never allocate, and such a vector can hold up to `usize::MAX` of them.
+    #[doc(alias = "MyVector")]
+    pub struct MyVec<T> {
+        // invariant: the first `len` slots of `buf` are initialized, and `len <= buf.capacity()`
+        buf: RawBuf<T>,
+        len: usize,
+    }
+
+    impl<T> MyVec<T> {
+        /// Create an empty vector, without allocating.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                buf: RawBuf::new(),
+                len: 0,
+            }
+        }
+
+        /// Create an empty vector with room for at least `capacity` values before it needs to reallocate.
+        ///
+        /// # Panics
+        ///
+        /// If the buffer would be larger than `isize::MAX` bytes.
+        #[inline]
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                buf: RawBuf::with_capacity(capacity),
+                len: 0,
+            }
+        }
+
+        /// Number of values in the vector.
+        #[doc(alias = "size")]
+        #[inline]
+        pub const fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Number of values the vector can hold before it needs to reallocate.
+        ///
+        /// For zero-sized types, this is always `usize::MAX`.
+        #[inline]
+        pub const fn capacity(&self) -> usize {
+            self.buf.capacity()
+        }
+
+        /// Add `value` to the end of the vector, doubling the capacity if it is full.
+        ///
+        /// # Panics
+        ///
+        /// If the new capacity would be larger than `isize::MAX` bytes (or, for zero-sized types, if the length overflows).
+        #[doc(alias = "push_back")]
+        pub fn push(&mut self, value: T) {
+            if self.len == self.buf.capacity() {
+                self.buf.reserve(self.len, 1);
+            }
+            // SAFETY: there is room for at least one more value, and the slot at `len` is not initialized (so nothing is overwritten)
+            unsafe { self.buf.ptr().add(self.len).write(value) };
+            self.len += 1;
+        }
+
+        /// Remove the last value from the vector and return it, or `None` if it is empty.
+        #[doc(alias = "pop_back")]
+        pub fn pop(&mut self) -> Option<T> {
+            if self.len == 0 {
+                return None;
+            }
+            self.len -= 1;
+            // SAFETY: the slot at the old last index was initialized, and is now outside of `len` so it is never read again
+            Some(unsafe { self.buf.ptr().add(self.len).read() })
+        }
+
+        /// Insert `value` at `index`, shifting every value after it one place to the right.
+        ///
+        /// # Panics
+        ///
+        /// If `index > len` (inserting at `len` is the same as [`push`](Self::push)), or if the vector can't grow.
+        #[track_caller]
+        pub fn insert(&mut self, index: usize, value: T) {
+            let len = self.len;
+            assert!(
+                index <= len,
+                "insertion index (is {index}) should be <= len (is {len})"
+            );
+            // grow before opening the gap, so a panic here leaves the vector untouched
+            if len == self.buf.capacity() {
+                self.buf.reserve(len, 1);
+            }
+            // SAFETY: `index <= len < capacity`, so both the source and the shifted destination are in bounds
+            unsafe {
+                let slot = self.buf.ptr().add(index);
+                // open the gap (`copy` allows the ranges to overlap); the old slot at `index` is now a bitwise duplicate
+                ptr::copy(slot, slot.add(1), len - index);
+                // fill the gap without dropping the duplicate, which now lives at `index + 1`
+                slot.write(value);
+            }
+            self.len = len + 1;
+        }
+
+        /// Remove and return the value at `index`, shifting every value after it one place to the left.
+        ///
+        /// This is O(n); use [`swap_remove`](Self::swap_remove) instead if the order doesn't matter.
+        ///
+        /// # Panics
+        ///
+        /// If `index >= len`.
+        #[track_caller]
+        pub fn remove(&mut self, index: usize) -> T {
+            let len = self.len;
+            assert!(
+                index < len,
+                "removal index (is {index}) should be < len (is {len})"
+            );
+            // SAFETY: `index < len`, so the value is initialized, and its slot is overwritten by the shift right after reading it
+            unsafe {
+                let slot = self.buf.ptr().add(index);
+                let value = slot.read();
+                ptr::copy(slot.add(1), slot, len - index - 1);
+                self.len = len - 1;
+                value
+            }
+        }
+
+        /// Remove and return the value at `index` in O(1), by moving the last value into its place.
+        ///
+        /// # Panics
+        ///
+        /// If `index >= len`.
+        #[track_caller]
+        pub fn swap_remove(&mut self, index: usize) -> T {
+            let len = self.len;
+            assert!(
+                index < len,
+                "swap_remove index (is {index}) should be < len (is {len})"
+            );
+            // SAFETY: `index` and `len - 1` are both initialized; the last slot is forgotten (by shrinking `len`) after it is moved
+            unsafe {
+                let base = self.buf.ptr();
+                let value = base.add(index).read();
+                // `copy` (rather than `copy_nonoverlapping`), since `index` may be the last slot itself
+                ptr::copy(base.add(len - 1), base.add(index), 1);
+                self.len = len - 1;
+                value
+            }
+        }
+
+        /// Get a reference to the value at `index`, or `None` if it is out of bounds.
+        #[doc(alias = "at")]
+        #[inline]
+        pub fn get(&self, index: usize) -> Option<&T> {
+            if index >= self.len {
+                return None;
+            }
+            // SAFETY: every slot below `len` is initialized, and the reference borrows `self`
+            Some(unsafe { &*self.buf.ptr().add(index) })
+        }
+
+        /// Get a mutable reference to the value at `index`, or `None` if it is out of bounds.
+        #[inline]
+        pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+            if index >= self.len {
+                return None;
+            }
+            // SAFETY: every slot below `len` is initialized, and the reference mutably borrows `self`
+            Some(unsafe { &mut *self.buf.ptr().add(index) })
+        }
+
+        /// Drop every value in the vector, keeping its capacity.
+        pub fn clear(&mut self) {
+            let values = ptr::slice_from_raw_parts_mut(self.buf.ptr(), self.len);
+            // forget the values first, so that a panicking destructor can't lead to a double drop
+            self.len = 0;
+            // SAFETY: the values were initialized, and are now outside of `len` so they are never accessed again
+            unsafe { values.drop_in_place() };
+        }
+
+        /// View the values as a slice.
+        #[inline]
+        pub const fn as_slice(&self) -> &[T] {
+            // SAFETY: the first `len` slots are initialized (and the pointer is aligned and non-null even when nothing is allocated)
+            unsafe { slice::from_raw_parts(self.buf.ptr(), self.len) }
+        }
+
+        /// View the values as a mutable slice.
+        #[inline]
+        pub const fn as_mut_slice(&mut self) -> &mut [T] {
+            // SAFETY: the first `len` slots are initialized, and the slice mutably borrows `self`
+            unsafe { slice::from_raw_parts_mut(self.buf.ptr(), self.len) }
+        }
+
+        /// Shorten the vector to `len` values, dropping the rest (or do nothing if it is already that short).
+        ///
+        /// The capacity is unchanged.
+        pub fn truncate(&mut self, len: usize) {
+            if len >= self.len {
+                return;
+            }
+            // SAFETY: `len < self.len`, so the tail is in bounds
+            let tail =
+                ptr::slice_from_raw_parts_mut(unsafe { self.buf.ptr().add(len) }, self.len - len);
+            // forget the tail first, so that a panicking destructor can't lead to a double drop
+            self.len = len;
+            // SAFETY: the tail was initialized, and is now outside of `len` so it is never accessed again
+            unsafe { tail.drop_in_place() };
+        }
+
+        /// Keep only the values for which `keep` returns `true`, in their original order, dropping the rest.
+        ///
+        /// This is O(n): every kept value is moved at most once, straight to its final position.
+        /// If `keep` (or a destructor) panics, the values that were already removed stay removed,
+        /// the rest are kept, and nothing is dropped twice.
+        pub fn retain_mut(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+            /// Closes the gap left by the removed values, even if `keep` panics.
+            struct BackshiftOnDrop<'a, T> {
+                vec: &'a mut MyVec<T>,
+                // invariant: the slots in `0..processed - deleted` and `processed..original_len` are initialized
+                processed: usize,
+                deleted: usize,
+                original_len: usize,
+            }
+
+            impl<T> Drop for BackshiftOnDrop<'_, T> {
+                fn drop(&mut self) {
+                    let base = self.vec.buf.ptr();
+                    if self.deleted > 0 {
+                        // SAFETY: the unprocessed values are initialized, and move left into the gap (`copy` allows overlap)
+                        unsafe {
+                            ptr::copy(
+                                base.add(self.processed),
+                                base.add(self.processed - self.deleted),
+                                self.original_len - self.processed,
+                            );
+                        }
+                    }
+                    self.vec.len = self.original_len - self.deleted;
+                }
+            }
+
+            let original_len = self.len;
+            // while the gap is open, act as if the vector is empty, in case the guard is somehow skipped
+            self.len = 0;
+            let mut guard = BackshiftOnDrop {
+                vec: self,
+                processed: 0,
+                deleted: 0,
+                original_len,
+            };
+            let base = guard.vec.buf.ptr();
+            while guard.processed < original_len {
+                // SAFETY: `processed < original_len`, so the value is initialized, and nothing else refers to it
+                let current = unsafe { &mut *base.add(guard.processed) };
+                if keep(current) {
+                    if guard.deleted > 0 {
+                        // SAFETY: the destination is in the gap, so it is uninitialized (or was moved out of), and it doesn't overlap `current`
+                        unsafe {
+                            ptr::copy_nonoverlapping(
+                                current,
+                                base.add(guard.processed - guard.deleted),
+                                1,
+                            )
+                        };
+                    }
+                    guard.processed += 1;
+                } else {
+                    // count the value as removed before dropping it, so that a panicking destructor can't lead to a double drop
+                    guard.processed += 1;
+                    guard.deleted += 1;
+                    // SAFETY: the value is initialized, and is now part of the gap so it is never accessed again
+                    unsafe { ptr::from_mut(current).drop_in_place() };
+                }
+            }
+            // the guard closes the (now empty) tail of the gap and restores the length
+        }
+
+        /// Remove consecutive values for which `same_bucket(value, previous)` returns `true`, keeping the first of each run.
+        ///
+        /// `previous` is the last value that was kept. If `same_bucket` (or a destructor) panics,
+        /// the values that were already removed stay removed, the rest are kept, and nothing is dropped twice.
+        pub fn dedup_by(&mut self, mut same_bucket: impl FnMut(&mut T, &mut T) -> bool) {
+            /// Closes the gap left by the removed values, even if `same_bucket` panics.
+            struct FillGapOnDrop<'a, T> {
+                vec: &'a mut MyVec<T>,
+                // invariant: the slots in `0..write` and `read..original_len` are initialized, and `write <= read`
+                read: usize,
+                write: usize,
+                original_len: usize,
+            }
+
+            impl<T> Drop for FillGapOnDrop<'_, T> {
+                fn drop(&mut self) {
+                    let base = self.vec.buf.ptr();
+                    let unread = self.original_len - self.read;
+                    // SAFETY: the unread values are initialized, and move left into the gap (`copy` allows overlap)
+                    unsafe { ptr::copy(base.add(self.read), base.add(self.write), unread) };
+                    self.vec.len = self.write + unread;
+                }
+            }
+
+            let original_len = self.len;
+            if original_len <= 1 {
+                return;
+            }
+            self.len = 0;
+            let mut guard = FillGapOnDrop {
+                vec: self,
+                read: 1,
+                write: 1,
+                original_len,
+            };
+            let base = guard.vec.buf.ptr();
+            while guard.read < original_len {
+                // SAFETY: `write - 1 < write <= read < original_len`, so both values are initialized and distinct
+                let (current, previous) =
+                    unsafe { (&mut *base.add(guard.read), &mut *base.add(guard.write - 1)) };
+                if same_bucket(current, previous) {
+                    guard.read += 1;
+                    // SAFETY: the value is initialized, and is now part of the gap so it is never accessed again
+                    unsafe { ptr::from_mut(current).drop_in_place() };
+                } else {
+                    if guard.read != guard.write {
+                        // SAFETY: the destination is in the gap, so it doesn't overlap `current`
+                        unsafe { ptr::copy_nonoverlapping(current, base.add(guard.write), 1) };
+                    }
+                    guard.read += 1;
+                    guard.write += 1;
+                }
+            }
+            // the guard restores the length (with nothing left to move)
+        }
+
+        /// Remove the values in `range` and return an iterator over them.
+        ///
+        /// The values after the range are moved into its place when the iterator is dropped,
+        /// whether or not every removed value was yielded (the rest are dropped).
+        ///
+        /// # Leaking
+        ///
+        /// The vector is shortened to the start of the range as soon as the iterator is created.
+        /// If the iterator is leaked (say, with [`mem::forget`](core::mem::forget)) instead of being dropped,
+        /// the vector stays that short, and the removed values as well as the ones after the range are leaked too.
+        /// This is memory-safe, it just loses values.
+        ///
+        /// # Panics
+        ///
+        /// If the range starts after it ends, or ends after `len`.
+        #[track_caller]
+        pub fn drain(&mut self, range: impl RangeBounds<usize>) -> Drain<'_, T> {
+            let len = self.len;
+            let Range { start, end } = super::resolve_range(range, len);
+            // the drained values and the tail are now outside of `len`, so leaking the `Drain` can only leak them
+            self.len = start;
+            Drain {
+                vec: self,
+                start,
+                end,
+                tail_start: end,
+                tail_len: len - end,
+            }
+        }
+
+        /// Replace the values in `range` with the values from `replace_with`, and return an iterator over the removed values.
+        ///
+        /// The replacement happens when the iterator is dropped (whether or not every removed value was yielded),
+        /// and `replace_with` may yield more or fewer values than it replaces.
+        /// If it yields more, the tail is moved at most twice: once by its lower size hint, and once more for the rest.
+        ///
+        /// Like [`drain`](Self::drain), leaking the iterator leaks the removed values, the tail, and the replacements.
+        ///
+        /// # Panics
+        ///
+        /// If the range starts after it ends, or ends after `len`.
+        #[track_caller]
+        pub fn splice<I: IntoIterator<Item = T>>(
+            &mut self,
+            range: impl RangeBounds<usize>,
+            replace_with: I,
+        ) -> Splice<'_, I::IntoIter> {
+            Splice {
+                drain: self.drain(range),
+                replace_with: replace_with.into_iter(),
+            }
+        }
+
+        /// Move the values from `at` onwards into a new vector, leaving the first `at` values (and the capacity) in this one.
+        ///
+        /// # Panics
+        ///
+        /// If `at > len`.
+        #[track_caller]
+        pub fn split_off(&mut self, at: usize) -> Self {
+            let len = self.len;
+            assert!(
+                at <= len,
+                "`at` split index (is {at}) should be <= len (is {len})"
+            );
+            let mut other = Self::with_capacity(len - at);
+            // SAFETY: the values in `at..len` are initialized, the new buffer has room for them, and they are forgotten here
+            // (by shrinking `len`) once they are moved
+            unsafe {
+                ptr::copy_nonoverlapping(self.buf.ptr().add(at), other.buf.ptr(), len - at);
+            }
+            self.len = at;
+            other.len = len - at;
+            other
+        }
+
+        /// Move every value from `other` onto the end of this vector, in one copy.
+        ///
+        /// `other` is left empty, but keeps its capacity.
+        ///
+        /// # Panics
+        ///
+        /// If the new capacity would be larger than `isize::MAX` bytes (or, for zero-sized types, if the length overflows).
+        pub fn append(&mut self, other: &mut Self) {
+            let count = other.len;
+            self.reserve(count);
+            // SAFETY: the values in `other` are initialized, there is room for them after `len`, the buffers are distinct
+            // (`other` is a separate `&mut`), and `other` forgets them (by shrinking its `len`) once they are moved
+            unsafe {
+                ptr::copy_nonoverlapping(other.buf.ptr(), self.buf.ptr().add(self.len), count);
+            }
+            other.len = 0;
+            self.len += count;
+        }
+
+        /// Make room for at least `additional` more values, so that many pushes in a row don't reallocate more than once.
+        ///
+        /// Like pushing, this grows to at least double the old capacity. Zero-sized types never need to grow.
+        ///
+        /// # Panics
+        ///
+        /// If the new capacity would be larger than `isize::MAX` bytes (or, for zero-sized types, if the length overflows).
+        pub fn reserve(&mut self, additional: usize) {
+            self.buf.reserve(self.len, additional);
+        }
+
+        /// Make room for exactly `additional` more values, without the extra room that [`reserve`](Self::reserve) leaves.
+        ///
+        /// # Panics
+        ///
+        /// If the new capacity would be larger than `isize::MAX` bytes (or, for zero-sized types, if the length overflows).
+        pub fn reserve_exact(&mut self, additional: usize) {
+            self.buf.reserve_exact(self.len, additional);
+        }
+
+        /// Like [`reserve`](Self::reserve), but returns an error (leaving the vector unchanged) instead of panicking,
+        /// or aborting when the allocator runs out of memory.
+        pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+            self.buf.try_reserve(self.len, additional)
+        }
+
+        /// Like [`reserve_exact`](Self::reserve_exact), but returns an error (leaving the vector unchanged) instead of panicking,
+        /// or aborting when the allocator runs out of memory.
+        pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+            self.buf.try_reserve_exact(self.len, additional)
+        }
+
+        /// Reallocate the buffer down to `max(len, min_capacity)` values (or do nothing if it is already that small).
+        pub fn shrink_to(&mut self, min_capacity: usize) {
+            let new_cap = self.len.max(min_capacity);
+            if new_cap < self.buf.capacity() {
+                self.buf.set_capacity(new_cap);
+            }
+        }
+    }
+
+    /// Translation of destructor.
+    impl<T> Drop for MyVec<T> {
+        fn drop(&mut self) {
+            // drop the values, then `buf` frees the allocation
+            self.clear();
+        }
+    }
+
+    /// Owning iterator over the values of a [`MyVec`], created by [`MyVec::into_iter`](IntoIterator::into_iter).
+    ///
+    /// The allocation is freed (along with any values that weren't yielded) when the iterator is dropped.
+    pub struct IntoIter<T> {
+        // invariant: exactly the slots in `start..end` are initialized, and `start <= end <= buf.capacity()`
+        buf: RawBuf<T>,
+        start: usize,
+        end: usize,
+    }
+
+    impl<T> IntoIter<T> {
+        /// View the values that haven't been yielded yet.
+        #[inline]
+        pub fn as_slice(&self) -> &[T] {
+            // SAFETY: the slots in `start..end` are initialized
+            unsafe { slice::from_raw_parts(self.buf.ptr().add(self.start), self.end - self.start) }
+        }
+
+        /// Mutably view the values that haven't been yielded yet.
+        #[inline]
+        pub fn as_mut_slice(&mut self) -> &mut [T] {
+            // SAFETY: the slots in `start..end` are initialized, and the slice mutably borrows `self`
+            unsafe {
+                slice::from_raw_parts_mut(self.buf.ptr().add(self.start), self.end - self.start)
+            }
+        }
+    }
+
+    /// Rust-specific helper to move the values out of a vector, from front to back.
+    impl<T> IntoIterator for MyVec<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+
+        fn into_iter(self) -> IntoIter<T> {
+            // the values (and the buffer) now belong to the iterator, so the vector must not drop them
+            let this = ManuallyDrop::new(self);
+            IntoIter {
+                // SAFETY: `this` is never used (or dropped) again, so this is a move out of it
+                buf: unsafe { ptr::read(&this.buf) },
+                start: 0,
+                end: this.len,
+            }
+        }
+    }
+
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            // SAFETY: the slot at `start` is initialized, and is never read again once `start` moves past it
+            let value = unsafe { self.buf.ptr().add(self.start).read() };
+            self.start += 1;
+            Some(value)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.end - self.start;
+            (len, Some(len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for IntoIter<T> {
+        fn next_back(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            self.end -= 1;
+            // SAFETY: the slot at the old `end - 1` is initialized, and is never read again now that `end` has moved before it
+            Some(unsafe { self.buf.ptr().add(self.end).read() })
+        }
+    }
+
+    /// Draining iterator over a range of a [`MyVec`], created by [`MyVec::drain`].
+    pub struct Drain<'a, T> {
+        // invariant: the first `vec.len` slots are initialized, the slots in `start..end` still hold the values that haven't
+        // been yielded, and the `tail_len` slots from `tail_start` hold the values after the range; every other slot up to
+        // `tail_start` is part of the gap (`vec.len` only moves past `start` once every value has been yielded, see `Splice`)
+        vec: &'a mut MyVec<T>,
+        start: usize,
+        end: usize,
+        tail_start: usize,
+        tail_len: usize,
+    }
+
+    impl<T> Drain<'_, T> {
+        /// View the removed values that haven't been yielded yet.
+        #[inline]
+        pub fn as_slice(&self) -> &[T] {
+            // SAFETY: the slots in `start..end` are initialized
+            unsafe {
+                slice::from_raw_parts(self.vec.buf.ptr().add(self.start), self.end - self.start)
+            }
+        }
+    }
+
+    impl<T> Iterator for Drain<'_, T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            // SAFETY: the slot at `start` is initialized, and is never read again once `start` moves past it
+            let value = unsafe { self.vec.buf.ptr().add(self.start).read() };
+            self.start += 1;
+            Some(value)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.end - self.start;
+            (len, Some(len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for Drain<'_, T> {
+        fn next_back(&mut self) -> Option<T> {
+            if self.start == self.end {
+                return None;
+            }
+            self.end -= 1;
+            // SAFETY: the slot at the old `end - 1` is initialized, and is never read again now that `end` has moved before it
+            Some(unsafe { self.vec.buf.ptr().add(self.end).read() })
+        }
+    }
+
+    impl<T> Drain<'_, T> {
+        /// Fill the gap (after every value has been yielded) from `replace_with`, returning whether it was filled completely.
+        fn fill(&mut self, replace_with: &mut impl Iterator<Item = T>) -> bool {
+            debug_assert_eq!(self.start, self.end, "filling the gap before draining it");
+            while self.vec.len < self.tail_start {
+                let Some(value) = replace_with.next() else {
+                    return false;
+                };
+                // SAFETY: the slot is part of the gap (so it is uninitialized), and is counted as initialized right after
+                unsafe { self.vec.buf.ptr().add(self.vec.len).write(value) };
+                self.vec.len += 1;
+            }
+            true
+        }
+
+        /// Move the tail `additional` slots to the right, widening the gap.
+        fn move_tail(&mut self, additional: usize) {
+            let used = self.tail_start + self.tail_len;
+            self.vec.buf.reserve(used, additional);
+            let base = self.vec.buf.ptr();
+            let new_tail_start = self.tail_start + additional;
+            // SAFETY: the tail is initialized, and there is room for it at its new position (`copy` allows overlap)
+            unsafe {
+                ptr::copy(
+                    base.add(self.tail_start),
+                    base.add(new_tail_start),
+                    self.tail_len,
+                )
+            };
+            self.tail_start = new_tail_start;
+        }
+    }
+
+    /// Splicing iterator over a range of a [`MyVec`], created by [`MyVec::splice`].
+    ///
+    /// It yields the removed values, and puts the replacements in their place when it is dropped.
+    pub struct Splice<'a, I: Iterator> {
+        drain: Drain<'a, I::Item>,
+        replace_with: I,
+    }
+
+    impl<I: Iterator> Splice<'_, I> {
+        /// View the removed values that haven't been yielded yet.
+        #[inline]
+        pub fn as_slice(&self) -> &[I::Item] {
+            self.drain.as_slice()
+        }
+    }
+
+    impl<I: Iterator> Iterator for Splice<'_, I> {
+        type Item = I::Item;
+
+        #[inline]
+        fn next(&mut self) -> Option<I::Item> {
+            self.drain.next()
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.drain.size_hint()
+        }
+    }
+
+    impl<I: Iterator> DoubleEndedIterator for Splice<'_, I> {
+        #[inline]
+        fn next_back(&mut self) -> Option<I::Item> {
+            self.drain.next_back()
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Drops the values that weren't yielded, fills the gap with the replacements, then (through the `Drain`) moves the tail
+    /// back to close whatever is left of the gap.
+    impl<I: Iterator> Drop for Splice<'_, I> {
+        fn drop(&mut self) {
+            self.drain.by_ref().for_each(drop);
+            if self.drain.tail_len == 0 {
+                // nothing to move out of the way
+                self.drain.vec.extend(self.replace_with.by_ref());
+                return;
+            }
+            if self.drain.fill(&mut self.replace_with) {
+                // there may be more replacements: first make room for as many as the iterator promises
+                let (lower, _) = self.replace_with.size_hint();
+                if lower > 0 {
+                    self.drain.move_tail(lower);
+                    if !self.drain.fill(&mut self.replace_with) {
+                        return;
+                    }
+                }
+                // then collect the rest, so the tail only has to move once more
+                let mut rest = self.replace_with.by_ref().collect::<MyVec<_>>().into_iter();
+                if rest.len() > 0 {
+                    self.drain.move_tail(rest.len());
+                    let filled = self.drain.fill(&mut rest);
+                    debug_assert!(filled && rest.len() == 0);
+                }
+            }
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Drops the values that weren't yielded, then moves the tail back to close the gap.
+    impl<T> Drop for Drain<'_, T> {
+        fn drop(&mut self) {
+            /// Moves the tail back even if a destructor panics.
+            struct MoveTail<'r, 'a, T>(&'r mut Drain<'a, T>);
+
+            impl<T> Drop for MoveTail<'_, '_, T> {
+                fn drop(&mut self) {
+                    let drain = &mut *self.0;
+                    let base = drain.vec.buf.ptr();
+                    let len = drain.vec.len;
+                    // SAFETY: the tail is initialized, and `len <= tail_start` so it moves left into the gap (`copy` allows overlap)
+                    unsafe { ptr::copy(base.add(drain.tail_start), base.add(len), drain.tail_len) };
+                    drain.vec.len = len + drain.tail_len;
+                }
+            }
+
+            // SAFETY: `start <= end <= capacity`, so the pointer is in bounds
+            let remaining = ptr::slice_from_raw_parts_mut(
+                unsafe { self.vec.buf.ptr().add(self.start) },
+                self.end - self.start,
+            );
+            // forget the values first, so that a panicking destructor can't lead to a double drop
+            self.start = self.end;
+            let _move_tail = MoveTail(self);
+            // SAFETY: the remaining values are initialized, and are never accessed again (the tail is moved over them)
+            unsafe { remaining.drop_in_place() };
+        }
+    }
+
+    /// Translation of destructor.
+    impl<T> Drop for IntoIter<T> {
+        fn drop(&mut self) {
+            let remaining = ptr::from_mut(self.as_mut_slice());
+            // forget the values first, so that a panicking destructor can't lead to a double drop
+            self.start = self.end;
+            // SAFETY: the remaining values are initialized, and are never accessed again (`buf` frees the allocation right after this)
+            unsafe { remaining.drop_in_place() };
+        }
+    }
+}
+
+impl<T> MyVec<T> {
+    /// Check whether the vector has no values.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reallocate the buffer to hold exactly `len` values, freeing it entirely if the vector is empty.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+
+    /// Keep only the values for which `keep` returns `true`, in their original order, dropping the rest.
+    ///
+    /// See [`retain_mut`](Self::retain_mut) for details, including what happens if `keep` panics.
+    #[inline]
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        self.retain_mut(|value| keep(value));
+    }
+
+    /// Remove consecutive values that map to the same key, keeping the first of each run.
+    #[inline]
+    pub fn dedup_by_key<K: PartialEq>(&mut self, mut key: impl FnMut(&mut T) -> K) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Iterate over references to the values, from front to back.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Iterate over mutable references to the values, from front to back.
+    #[inline]
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T: PartialEq> MyVec<T> {
+    /// Remove consecutive duplicate values, keeping the first of each run.
+    ///
+    /// If the vector is sorted, this removes every duplicate.
+    #[inline]
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+}
+
+/// Rust-specific helper to turn any range of indices into a checked `start..end`, with the same panics as slice indexing.
+///
+/// # Panics
+///
+/// If the range starts after it ends, or ends after `len`.
+#[track_caller]
+pub(crate) fn resolve_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice from after maximum usize")),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice up to maximum usize")),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end,
+        "slice index starts at {start} but ends at {end}"
+    );
+    assert!(
+        end <= len,
+        "range end index {end} out of range for slice of length {len}"
+    );
+    start..end
+}
+
+/// Translation of the default constructor into Rust.
+impl<T> Default for MyVec<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+///
+/// The clone's capacity is exactly its length, no matter how much spare capacity the original has.
+impl<T: Clone> Clone for MyVec<T> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::with_capacity(self.len());
+        clone.extend(self.iter().cloned());
+        clone
+    }
+
+    /// Translation of the copy assignment operator: reuses this vector's buffer (and its values, through
+    /// [`Clone::clone_from`]) where it can, only reallocating if `source` has more values than fit.
+    fn clone_from(&mut self, source: &Self) {
+        self.truncate(source.len());
+        let (init, tail) = source.split_at(self.len());
+        self.clone_from_slice(init);
+        self.extend(tail.iter().cloned());
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way.
+impl<T: fmt::Debug> fmt::Debug for MyVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Translation of `operator==`: vectors are equal if they hold equal values in the same order, no matter their
+/// capacities.
+impl<T: PartialEq> PartialEq for MyVec<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: Eq> Eq for MyVec<T> {}
+
+/// Rust-specific helper to compare a vector's values with a slice, as in `vec == [1, 2, 3][..]`.
+impl<T: PartialEq> PartialEq<[T]> for MyVec<T> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        self[..] == *other
+    }
+}
+
+/// Rust-specific helper to compare a vector's values with a borrowed slice.
+impl<T: PartialEq> PartialEq<&[T]> for MyVec<T> {
+    #[inline]
+    fn eq(&self, other: &&[T]) -> bool {
+        self[..] == **other
+    }
+}
+
+/// Translation of `operator[]` for reading.
+impl<T> Index<usize> for MyVec<T> {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+/// Translation of `operator[]` for writing.
+impl<T> IndexMut<usize> for MyVec<T> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+/// Translation of `operator[]` for reading a range of values, as a slice.
+macro_rules! index_ranges {
+    ($($range:ty),* $(,)?) => {
+        $(
+            impl<T> Index<$range> for MyVec<T> {
+                type Output = [T];
+
+                #[inline]
+                #[track_caller]
+                fn index(&self, range: $range) -> &[T] {
+                    &self.as_slice()[range]
+                }
+            }
+
+            impl<T> IndexMut<$range> for MyVec<T> {
+                #[inline]
+                #[track_caller]
+                fn index_mut(&mut self, range: $range) -> &mut [T] {
+                    &mut self.as_mut_slice()[range]
+                }
+            }
+        )*
+    };
+}
+
+index_ranges!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeTo<usize>,
+    RangeToInclusive<usize>,
+);
+
+/// Rust-specific helper to use every slice method (sorting, searching, splitting, ...) directly on the vector.
+impl<T> Deref for MyVec<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+/// Rust-specific helper to use every mutating slice method directly on the vector.
+impl<T> DerefMut for MyVec<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+/// Rust-specific helper to visualize the removed values that haven't been yielded yet.
+impl<T: fmt::Debug> fmt::Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for Splice<'_, I> {}
+
+/// Rust-specific helper to visualize the removed values that haven't been yielded yet.
+impl<I: Iterator<Item: fmt::Debug>> fmt::Debug for Splice<'_, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Splice").field(&self.as_slice()).finish()
+    }
+}
+
+/// Rust-specific helper to iterate over a vector by reference, as in `for value in &vec`.
+impl<'a, T> IntoIterator for &'a MyVec<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Rust-specific helper to iterate over a vector by mutable reference, as in `for value in &mut vec`.
+impl<'a, T> IntoIterator for &'a mut MyVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Rust-specific helper to allow collecting an iterator into a vector.
+impl<T> FromIterator<T> for MyVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+/// Rust-specific helper to push every value from an iterator.
+impl<T> Extend<T> for MyVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        // make room for the least the iterator promises to yield up front (pushing grows further if it yields more)
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Rust-specific helper to push a copy of every value from an iterator of references, such as a slice.
+impl<'a, T: Copy + 'a> Extend<&'a T> for MyVec<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        allocator::TryReserveError,
+        test_alloc::count_allocations,
+        test_drop::{CountDrops, check_drops_once},
+        test_std::to_std,
+    };
+
+    #[test]
+    fn push_pop_round_trip() {
+        let mut vec = MyVec::new();
+        assert!(vec.is_empty());
+        for i in 0..100 {
+            vec.push(i);
+        }
+        assert_eq!(vec.len(), 100);
+        assert!(vec.capacity() >= 100);
+        for i in (0..100).rev() {
+            assert_eq!(vec.pop(), Some(i));
+        }
+        assert_eq!(vec.pop(), None);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn new_does_not_allocate() {
+        let (vec, allocations) = count_allocations(MyVec::<u64>::new);
+        assert_eq!(allocations, 0);
+        assert_eq!(vec.capacity(), 0);
+        let (vec, allocations) = count_allocations(|| MyVec::<u64>::with_capacity(10));
+        assert_eq!(allocations, 1);
+        assert_eq!(vec.capacity(), 10);
+    }
+
+    #[test]
+    fn push_doubles_capacity() {
+        let mut vec = MyVec::new();
+        let mut capacities = std::vec::Vec::new();
+        for i in 0..20 {
+            vec.push(i);
+            if capacities.last() != Some(&vec.capacity()) {
+                capacities.push(vec.capacity());
+            }
+        }
+        assert_eq!(capacities, [4, 8, 16, 32]);
+    }
+
+    #[test]
+    fn get_and_index() {
+        let mut vec = MyVec::new();
+        vec.push(std::string::String::from("a"));
+        vec.push(std::string::String::from("b"));
+        assert_eq!(vec.get(1).map(|s| s.as_str()), Some("b"));
+        assert_eq!(vec.get(2), None);
+        vec.get_mut(0).unwrap().push('!');
+        vec[1].push('?');
+        assert_eq!((vec[0].as_str(), vec[1].as_str()), ("a!", "b?"));
+    }
+
+    #[test]
+    #[should_panic = "index out of bounds: the len is 3 but the index is 3"]
+    fn index_out_of_bounds() {
+        let mut vec = MyVec::new();
+        vec.push(1);
+        vec.push(2);
+        vec.push(3);
+        let _ = vec[3];
+    }
+
+    #[test]
+    #[should_panic = "index out of bounds: the len is 0 but the index is 0"]
+    fn index_mut_out_of_bounds() {
+        let mut vec = MyVec::<i32>::new();
+        vec[0] = 1;
+    }
+
+    #[test]
+    fn zst_never_allocates() {
+        let ((), allocations) = count_allocations(|| {
+            let mut vec = MyVec::new();
+            assert_eq!(vec.capacity(), usize::MAX);
+            for _ in 0..1000 {
+                vec.push(());
+            }
+            assert_eq!(vec.len(), 1000);
+            assert_eq!(vec[999], ());
+            assert_eq!(vec.pop(), Some(()));
+            assert_eq!(vec.len(), 999);
+        });
+        assert_eq!(allocations, 0);
+    }
+
+    #[test]
+    fn drops_each_value_once() {
+        let drops = core::cell::Cell::new(0);
+        let popped = check_drops_once(&drops, MyVec::new(), MyVec::push, MyVec::pop, |vec| {
+            vec.clear();
+            assert!(vec.is_empty());
+        });
+        // the last value pushed is the first popped
+        assert_eq!(popped, 9);
+    }
+
+    /// Build a vector from the given values.
+    fn my_vec_of<T: Clone>(values: &[T]) -> MyVec<T> {
+        let mut vec = MyVec::new();
+        for value in values {
+            vec.push(value.clone());
+        }
+        vec
+    }
+
+    #[test]
+    fn insert_front_middle_end() {
+        let mut vec = my_vec_of(&[2, 4]);
+        vec.insert(0, 1);
+        vec.insert(2, 3);
+        vec.insert(4, 5);
+        assert_eq!(to_std(&vec), [1, 2, 3, 4, 5]);
+        // inserting into a full vector grows it
+        let mut vec = MyVec::with_capacity(1);
+        vec.insert(0, 'b');
+        vec.insert(0, 'a');
+        assert_eq!(to_std(&vec), ['a', 'b']);
+    }
+
+    #[test]
+    fn remove_shifts_left() {
+        let mut vec = my_vec_of(&[1, 2, 3, 4]);
+        assert_eq!(vec.remove(1), 2);
+        assert_eq!(vec.remove(2), 4);
+        assert_eq!(to_std(&vec), [1, 3]);
+
+        let mut single = my_vec_of(&["only"]);
+        assert_eq!(single.remove(0), "only");
+        assert!(single.is_empty());
+    }
+
+    #[test]
+    fn swap_remove_moves_last() {
+        let mut vec = my_vec_of(&[1, 2, 3, 4]);
+        assert_eq!(vec.swap_remove(0), 1);
+        assert_eq!(to_std(&vec), [4, 2, 3]);
+        // removing the last value doesn't move anything
+        assert_eq!(vec.swap_remove(2), 3);
+        assert_eq!(to_std(&vec), [4, 2]);
+    }
+
+    #[test]
+    fn positional_mutation_drops_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec = MyVec::new();
+        for i in 0..5 {
+            vec.insert(0, CountDrops::new(i, &drops));
+        }
+        assert_eq!(drops.get(), 0);
+        assert_eq!(vec.remove(1).value, 3);
+        assert_eq!(vec.swap_remove(0).value, 4);
+        assert_eq!(drops.get(), 2);
+        core::mem::drop(vec);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn insert_out_of_bounds_leaves_vec_untouched() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec = MyVec::new();
+        vec.push(CountDrops::new(0, &drops));
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            vec.insert(2, CountDrops::new(1, &drops));
+        }));
+        assert!(result.is_err());
+        // only the rejected value was dropped, and no gap was opened
+        assert_eq!(drops.get(), 1);
+        assert_eq!(vec.len(), 1);
+        assert_eq!(vec[0].value, 0);
+    }
+
+    #[test]
+    #[should_panic = "insertion index (is 3) should be <= len (is 2)"]
+    fn insert_past_end() {
+        my_vec_of(&[1, 2]).insert(3, 3);
+    }
+
+    #[test]
+    #[should_panic = "removal index (is 2) should be < len (is 2)"]
+    fn remove_out_of_bounds() {
+        my_vec_of(&[1, 2]).remove(2);
+    }
+
+    #[test]
+    #[should_panic = "swap_remove index (is 0) should be < len (is 0)"]
+    fn swap_remove_empty() {
+        MyVec::<i32>::new().swap_remove(0);
+    }
+
+    #[test]
+    fn collect_and_iterate() {
+        let (mut vec, allocations) = count_allocations(|| (0..10).collect::<MyVec<_>>());
+        // the exact size hint means only one allocation is needed
+        assert_eq!(allocations, 1);
+        assert_eq!(vec.capacity(), 10);
+        assert_eq!(vec.iter().sum::<i32>(), 45);
+        for value in &mut vec {
+            *value *= 2;
+        }
+        assert_eq!(
+            (&vec)
+                .into_iter()
+                .rev()
+                .step_by(3)
+                .copied()
+                .collect::<std::vec::Vec<_>>(),
+            [18, 12, 6, 0]
+        );
+
+        vec.extend([100, 200]);
+        assert_eq!(vec.len(), 12);
+        assert_eq!(
+            vec.into_iter().rev().take(3).collect::<std::vec::Vec<_>>(),
+            [200, 100, 18]
+        );
+    }
+
+    #[test]
+    fn collect_filtered() {
+        // a filter cannot promise any values, so the vector grows as it goes
+        let vec = (0..100)
+            .filter(|value| value % 7 == 0)
+            .collect::<MyVec<_>>();
+        assert_eq!(vec.len(), 15);
+        assert_eq!(&vec[..3], [0, 7, 14]);
+        assert_eq!(vec.last(), Some(&98));
+        assert!(vec.capacity() >= vec.len());
+
+        let empty = (0..100).filter(|_| false).collect::<MyVec<i32>>();
+        assert_eq!((empty.len(), empty.capacity()), (0, 0));
+    }
+
+    #[test]
+    fn extend_with_range_and_slice() {
+        let mut vec = (0..3).collect::<MyVec<_>>();
+        let ((), allocations) = count_allocations(|| vec.extend(3..10));
+        // the range's exact size hint means only one reallocation is needed
+        assert_eq!(allocations, 1);
+        assert_eq!(vec.capacity(), 10);
+
+        vec.extend(&[10, 11][..]);
+        vec.extend([12, 13].iter());
+        assert_eq!(*vec, (0..14).collect::<std::vec::Vec<_>>());
+        vec.extend(&[] as &[i32]);
+        assert_eq!(vec.len(), 14);
+    }
+
+    #[test]
+    fn clone_is_deep_and_compact() {
+        use std::string::{String, ToString};
+
+        let mut vec = MyVec::with_capacity(10);
+        vec.extend(["a", "bb", "ccc"].map(String::from));
+        let (mut clone, allocations) = count_allocations(|| vec.clone());
+        // one allocation for the buffer, and one for each string
+        assert_eq!(allocations, 4);
+        assert_eq!((clone.len(), clone.capacity()), (3, 3));
+        assert_eq!(clone[..], vec[..]);
+        assert_ne!(clone[0].as_ptr(), vec[0].as_ptr());
+        clone[0].push('!');
+        assert_eq!((&*vec[0], &*clone[0]), ("a", "a!"));
+
+        let empty = MyVec::<String>::new();
+        assert_eq!(count_allocations(|| empty.clone().capacity()), (0, 0));
+
+        // cloning into a vector with spare room reuses its buffer
+        let mut target = MyVec::with_capacity(10);
+        target.extend((0..5).map(|i| i.to_string()));
+        let ptr = target.as_ptr();
+        target.clone_from(&vec);
+        assert_eq!((target.as_ptr(), target.capacity()), (ptr, 10));
+        assert_eq!(target[..], vec[..]);
+        vec.extend((0..7).map(|i| i.to_string()));
+        target.clone_from(&vec);
+        assert_eq!((target.as_ptr(), target.capacity()), (ptr, 10));
+        assert_eq!(target[..], vec[..]);
+    }
+
+    #[test]
+    fn debug_and_equality() {
+        let vec: MyVec<_> = (1..=3).collect();
+        assert_eq!(std::format!("{vec:?}"), "[1, 2, 3]");
+        assert_eq!(std::format!("{:?}", MyVec::<()>::new()), "[]");
+        assert_eq!(
+            std::format!("{:#?}", ["a"].into_iter().collect::<MyVec<_>>()),
+            "[\n    \"a\",\n]"
+        );
+
+        assert!(vec == [1, 2, 3][..]);
+        let slice: &[i32] = &[1, 2, 3];
+        assert_eq!(vec, slice);
+        assert!(vec != [1, 2][..]);
+        // capacity doesn't matter, only the values
+        let mut roomy = MyVec::with_capacity(10);
+        roomy.extend([1, 2, 3]);
+        assert_eq!(vec, roomy);
+        roomy.push(4);
+        assert_ne!(vec, roomy);
+        assert_eq!(MyVec::<i32>::new(), MyVec::with_capacity(5));
+    }
+
+    #[test]
+    fn into_iter_both_ends() {
+        let vec: MyVec<_> = ["a", "b", "c", "d"].into_iter().collect();
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!((iter.next(), iter.next_back()), (Some("a"), Some("d")));
+        assert_eq!(std::format!("{iter:?}"), r#"IntoIter(["b", "c"])"#);
+        assert_eq!(
+            (iter.next_back(), iter.next_back(), iter.next()),
+            (Some("c"), Some("b"), None)
+        );
+    }
+
+    #[test]
+    fn into_iter_drops_rest() {
+        use crate::test_alloc::count_deallocations;
+
+        let drops = core::cell::Cell::new(0);
+        let vec: MyVec<_> = (0..6).map(|i| CountDrops::new(i, &drops)).collect();
+        let mut iter = vec.into_iter();
+        assert_eq!(iter.next().unwrap().value, 0);
+        assert_eq!(iter.next_back().unwrap().value, 5);
+        assert_eq!(drops.get(), 2);
+
+        let ((), deallocations) = count_deallocations(|| core::mem::drop(iter));
+        assert_eq!(deallocations, 1);
+        assert_eq!(drops.get(), 6);
+
+        // ZSTs work the same way
+        let units: MyVec<()> = core::iter::repeat_n((), 5).collect();
+        assert_eq!(units.into_iter().rev().count(), 5);
+    }
+
+    #[test]
+    fn truncate_drops_tail() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec: MyVec<_> = (0..5).map(|i| CountDrops::new(i, &drops)).collect();
+        vec.truncate(10);
+        assert_eq!((vec.len(), drops.get()), (5, 0));
+        vec.truncate(2);
+        assert_eq!((vec.len(), drops.get()), (2, 3));
+        assert_eq!(vec.capacity(), 5);
+        vec.truncate(0);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn drain_middle() {
+        let mut vec: MyVec<_> = (0..8).collect();
+        let mut drain = vec.drain(2..5);
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next_back(), Some(4));
+        assert_eq!(std::format!("{drain:?}"), "Drain([2, 3])");
+        assert_eq!(drain.collect::<std::vec::Vec<_>>(), [2, 3]);
+        assert_eq!(to_std(&vec), [0, 1, 5, 6, 7]);
+
+        assert_eq!(vec.drain(3..=4).collect::<std::vec::Vec<_>>(), [6, 7]);
+        assert_eq!(vec.drain(..).collect::<std::vec::Vec<_>>(), [0, 1, 5]);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn drop_half_consumed_drain() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec: MyVec<_> = (0..6).map(|i| CountDrops::new(i, &drops)).collect();
+        let mut drain = vec.drain(1..5);
+        assert_eq!(drain.next().unwrap().value, 1);
+        assert_eq!(drops.get(), 1);
+        core::mem::drop(drain);
+        // the three values that weren't yielded are dropped, and the tail moves back
+        assert_eq!(drops.get(), 4);
+        assert_eq!(
+            vec.iter().map(|v| v.value).collect::<std::vec::Vec<_>>(),
+            [0, 5]
+        );
+    }
+
+    #[test]
+    fn leaked_drain_leaks_rest() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec: MyVec<_> = (0..6).map(|i| CountDrops::new(i, &drops)).collect();
+        let mut drain = vec.drain(2..4);
+        assert_eq!(drain.next().unwrap().value, 2);
+        core::mem::forget(drain);
+        // the vector was shortened to the start of the range, and everything after it is lost (but never dropped)
+        assert_eq!(vec.len(), 2);
+        assert_eq!(drops.get(), 1);
+        vec.push(CountDrops::new(6, &drops));
+        core::mem::drop(vec);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    #[should_panic = "range end index 4 out of range for slice of length 3"]
+    fn drain_out_of_bounds() {
+        my_vec_of(&[1, 2, 3]).drain(1..4);
+    }
+
+    #[test]
+    #[should_panic = "slice index starts at 2 but ends at 1"]
+    fn drain_backwards() {
+        #[allow(clippy::reversed_empty_ranges)]
+        my_vec_of(&[1, 2, 3]).drain(2..1);
+    }
+
+    #[test]
+    fn split_off_and_append() {
+        let mut vec: MyVec<_> = (0..10).collect();
+        let mut tail = vec.split_off(6);
+        assert_eq!((vec.len(), vec.capacity()), (6, 10));
+        assert_eq!((tail.len(), tail.capacity()), (4, 4));
+        assert_eq!(tail[..], [6, 7, 8, 9]);
+
+        let mut end = vec.split_off(6);
+        assert!(end.is_empty());
+        end.append(&mut tail);
+        assert_eq!(end[..], [6, 7, 8, 9]);
+        assert_eq!((tail.len(), tail.capacity()), (0, 4));
+
+        vec.truncate(2);
+        vec.append(&mut end);
+        assert_eq!(vec[..], [0, 1, 6, 7, 8, 9]);
+        assert_eq!(vec.capacity(), 10);
+        assert_eq!(end.capacity(), 4);
+        let all = vec.split_off(0);
+        assert!(vec.is_empty());
+        assert_eq!(all.len(), 6);
+    }
+
+    #[test]
+    fn append_drops_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut front: MyVec<_> = (0..3).map(|i| CountDrops::new(i, &drops)).collect();
+        let mut back: MyVec<_> = (3..7).map(|i| CountDrops::new(i, &drops)).collect();
+        front.append(&mut back);
+        let split = front.split_off(5);
+        assert_eq!(drops.get(), 0);
+        core::mem::drop((front, back));
+        assert_eq!(drops.get(), 5);
+        core::mem::drop(split);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    #[should_panic = "`at` split index (is 3) should be <= len (is 2)"]
+    fn split_off_past_end() {
+        my_vec_of(&[1, 2]).split_off(3);
+    }
+
+    #[test]
+    fn splice_shorter_and_longer() {
+        let mut vec: MyVec<_> = (0..6).collect();
+        let removed: std::vec::Vec<_> = vec.splice(1..4, [10]).collect();
+        assert_eq!(removed, [1, 2, 3]);
+        assert_eq!(vec[..], [0, 10, 4, 5]);
+
+        // more replacements than removed values, with an exact size hint
+        let removed: std::vec::Vec<_> = vec.splice(1..2, [20, 21, 22, 23]).collect();
+        assert_eq!(removed, [10]);
+        assert_eq!(vec[..], [0, 20, 21, 22, 23, 4, 5]);
+
+        // an unknown number of replacements (the lower size hint is 0)
+        vec.splice(..1, (0..5).filter(|n| n % 2 == 0));
+        assert_eq!(vec[..], [0, 2, 4, 20, 21, 22, 23, 4, 5]);
+
+        // splicing at the end (no tail), and replacing everything
+        vec.splice(7.., [6, 7, 8]);
+        assert_eq!(vec[..], [0, 2, 4, 20, 21, 22, 23, 6, 7, 8]);
+        let removed: std::vec::Vec<_> = vec.splice(.., core::iter::empty()).collect();
+        assert_eq!(removed.len(), 10);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn splice_partially_consumed() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec: MyVec<_> = (0..6).map(|i| CountDrops::new(i, &drops)).collect();
+        let replacements = (10..13).map(|i| CountDrops::new(i, &drops));
+        let mut splice = vec.splice(1..5, replacements);
+        assert_eq!(splice.len(), 4);
+        assert_eq!(splice.next().unwrap().value, 1);
+        assert_eq!(drops.get(), 1);
+        assert_eq!(splice.as_slice().len(), 3);
+        core::mem::drop(splice);
+        // the three values that weren't yielded are dropped, and the replacements take their place
+        assert_eq!(drops.get(), 4);
+        assert_eq!(
+            vec.iter().map(|v| v.value).collect::<std::vec::Vec<_>>(),
+            [0, 10, 11, 12, 5]
+        );
+        core::mem::drop(vec);
+        assert_eq!(drops.get(), 9);
+    }
+
+    #[test]
+    fn retain_keeps_order() {
+        let mut vec: MyVec<_> = (0..10).collect();
+        vec.retain(|&n| n % 3 != 0);
+        assert_eq!(to_std(&vec), [1, 2, 4, 5, 7, 8]);
+        vec.retain_mut(|n| {
+            *n *= 10;
+            *n > 40
+        });
+        assert_eq!(to_std(&vec), [50, 70, 80]);
+    }
+
+    #[test]
+    fn retain_nothing() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec: MyVec<_> = (0..5).map(|i| CountDrops::new(i, &drops)).collect();
+        vec.retain(|_| false);
+        assert!(vec.is_empty());
+        assert_eq!(drops.get(), 5);
+        assert_eq!(vec.capacity(), 5);
+    }
+
+    #[test]
+    fn retain_panic_drops_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec: MyVec<_> = (0..8).map(|i| CountDrops::new(i, &drops)).collect();
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            vec.retain(|v| {
+                assert_ne!(v.value, 5, "predicate panicked");
+                v.value % 2 == 0
+            });
+        }));
+        assert!(result.is_err());
+        // 1 and 3 were removed before the panic, and everything from 5 on is kept
+        assert_eq!(drops.get(), 2);
+        assert_eq!(
+            vec.iter().map(|v| v.value).collect::<std::vec::Vec<_>>(),
+            [0, 2, 4, 5, 6, 7]
+        );
+        core::mem::drop(vec);
+        assert_eq!(drops.get(), 8);
+    }
+
+    #[test]
+    fn dedup_runs() {
+        let mut vec = my_vec_of(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        vec.dedup();
+        assert_eq!(to_std(&vec), [1, 2, 3, 1, 4]);
+
+        let mut words = my_vec_of(&["apple", "avocado", "banana", "cherry", "cranberry"]);
+        words.dedup_by_key(|word| word.as_bytes()[0]);
+        assert_eq!(to_std(&words), ["apple", "banana", "cherry"]);
+    }
+
+    #[test]
+    fn dedup_unique() {
+        let mut vec = my_vec_of(&[1, 2, 3, 4]);
+        vec.dedup();
+        assert_eq!(to_std(&vec), [1, 2, 3, 4]);
+        let mut empty = MyVec::<i32>::new();
+        empty.dedup();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn dedup_panic_drops_once() {
+        let drops = core::cell::Cell::new(0);
+        let mut vec: MyVec<_> = [0, 0, 1, 1, 2, 2]
+            .into_iter()
+            .map(|i| CountDrops::new(i, &drops))
+            .collect();
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            vec.dedup_by(|a, b| {
+                assert_ne!(a.value, 2, "comparison panicked");
+                a.value == b.value
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+        assert_eq!(
+            vec.iter().map(|v| v.value).collect::<std::vec::Vec<_>>(),
+            [0, 1, 2, 2]
+        );
+        core::mem::drop(vec);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn slice_methods_and_ranges() {
+        fn sum(values: &[i32]) -> i32 {
+            values.iter().sum()
+        }
+
+        let mut vec = my_vec_of(&[5, 3, 8, 1, 9, 2]);
+        vec.sort();
+        assert_eq!(vec[..], [1, 2, 3, 5, 8, 9]);
+        assert_eq!(vec.binary_search(&8), Ok(4));
+        assert_eq!(vec[1..3], [2, 3]);
+        assert_eq!(vec[4..], [8, 9]);
+        assert_eq!(vec[..=1], [1, 2]);
+        assert_eq!(vec[2..=2], [3]);
+        assert!(vec[..0].is_empty());
+        assert_eq!(sum(&vec), 28);
+
+        vec[1..4].reverse();
+        assert_eq!(vec.as_slice(), [1, 5, 3, 2, 8, 9]);
+        let (left, right) = vec.split_at_mut(3);
+        left.swap_with_slice(&mut right[..]);
+        assert_eq!(vec.as_slice(), [2, 8, 9, 1, 5, 3]);
+        assert!(matches!(*vec, [2, .., 3]));
+    }
+
+    #[test]
+    fn deref_to_slice() {
+        let mut vec: MyVec<i32> = (1..=4).collect();
+        assert_eq!(vec.iter().sum::<i32>(), 10);
+        assert_eq!((vec.first(), vec.last()), (Some(&1), Some(&4)));
+        assert!(vec.windows(2).all(|pair| pair[0] < pair[1]));
+        *vec.first_mut().unwrap() = 10;
+        vec.iter_mut().for_each(|value| *value *= 2);
+        assert_eq!(vec.as_slice(), [20, 4, 6, 8]);
+        assert_eq!(MyVec::<i32>::new().first(), None);
+    }
+
+    #[test]
+    #[should_panic = "range end index 7 out of range for slice of length 6"]
+    fn range_out_of_bounds() {
+        let vec = my_vec_of(&[0; 6]);
+        let _ = &vec[2..7];
+    }
+
+    #[test]
+    fn reserve_and_shrink() {
+        let mut vec = MyVec::<u32>::new();
+        vec.reserve(10);
+        assert_eq!(vec.capacity(), 10);
+        vec.extend(0..10);
+        // reserving grows geometrically, just like pushing
+        vec.reserve(1);
+        assert_eq!(vec.capacity(), 20);
+        vec.reserve_exact(15);
+        assert_eq!(vec.capacity(), 25);
+        // there is already enough room
+        let ((), allocations) = count_allocations(|| {
+            vec.reserve(15);
+            vec.reserve_exact(1);
+        });
+        assert_eq!(allocations, 0);
+        assert_eq!(vec.capacity(), 25);
+
+        vec.shrink_to(30);
+        assert_eq!(vec.capacity(), 25);
+        vec.shrink_to(12);
+        assert_eq!(vec.capacity(), 12);
+        vec.shrink_to(0);
+        assert_eq!(vec.capacity(), 10);
+        vec.truncate(3);
+        vec.shrink_to_fit();
+        assert_eq!(vec.capacity(), 3);
+        assert_eq!(vec[..], [0, 1, 2]);
+    }
+
+    #[test]
+    fn pointer_stable_until_growth() {
+        let mut vec = MyVec::with_capacity(8);
+        let ptr = vec.as_ptr();
+        for i in 0..8 {
+            vec.push(i);
+            assert_eq!(vec.as_ptr(), ptr);
+        }
+        // the first push past the capacity moves everything, at most once, into at least double the room
+        let ((), allocations) = count_allocations(|| vec.push(8));
+        assert!(allocations <= 1);
+        assert!(vec.capacity() >= 16);
+        let ptr = vec.as_ptr();
+        vec.extend(9..16);
+        assert_eq!(vec.as_ptr(), ptr);
+        assert!(vec.iter().copied().eq(0..16));
+    }
+
+    #[test]
+    fn shrink_empty_frees() {
+        use crate::test_alloc::count_deallocations;
+
+        let mut vec = MyVec::<u64>::with_capacity(8);
+        let ((), deallocations) = count_deallocations(|| vec.shrink_to_fit());
+        assert_eq!(deallocations, 1);
+        assert_eq!(vec.capacity(), 0);
+        // the vector still works after freeing its buffer
+        vec.push(1);
+        assert_eq!(vec[..], [1]);
+    }
+
+    #[test]
+    fn reserve_zst_is_no_op() {
+        let ((), allocations) = count_allocations(|| {
+            let mut vec = MyVec::<()>::new();
+            vec.reserve(100);
+            vec.reserve_exact(usize::MAX);
+            vec.shrink_to_fit();
+            assert_eq!(vec.capacity(), usize::MAX);
+            vec.push(());
+            assert_eq!(
+                vec.try_reserve(usize::MAX),
+                Err(TryReserveError::CapacityOverflow)
+            );
+        });
+        assert_eq!(allocations, 0);
+    }
+
+    #[test]
+    fn try_reserve_failure() {
+        use crate::test_alloc::failing_allocations_over;
+
+        let mut vec: MyVec<u64> = (0..4).collect();
+        let result = failing_allocations_over(64, || vec.try_reserve_exact(100));
+        assert_eq!(
+            result,
+            Err(TryReserveError::AllocError {
+                layout: core::alloc::Layout::array::<u64>(104).unwrap()
+            })
+        );
+        // the vector is untouched, and can still grow once memory is available again
+        assert_eq!((vec.capacity(), &vec[..]), (4, &[0, 1, 2, 3][..]));
+        assert_eq!(failing_allocations_over(64, || vec.try_reserve(4)), Ok(()));
+        assert_eq!(vec.capacity(), 8);
+        assert_eq!(
+            vec.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(
+            std::string::ToString::to_string(&vec.try_reserve(isize::MAX as usize).unwrap_err()),
+            "capacity overflow"
+        );
+    }
+
+    #[test]
+    #[should_panic = "capacity overflow"]
+    fn capacity_overflow() {
+        MyVec::<u64>::with_capacity(usize::MAX);
+    }
+}
@@ -0,0 +1,300 @@
+//! Growable heap buffer shared by the array-backed data structures in this crate.
+//!
+//! The buffer only manages the allocation itself: it never reads, writes, or drops any elements,
+//! so the data structures built on it are responsible for keeping track of which slots are initialized.
+
+pub(crate) use implementation::RawBuf;
+
+/// Unsafe-restraining module.
+#[allow(unsafe_code)]
+mod implementation {
+    use crate::allocator::TryReserveError;
+    use core::{alloc::Layout, marker::PhantomData, mem, ptr::NonNull};
+
+    /// Heap allocation with room for `capacity` values of `T`, none of which are assumed to be initialized.
+    ///
+    /// Zero-sized types never allocate, and always have a capacity of `usize::MAX`.
+    pub(crate) struct RawBuf<T> {
+        // invariant: if `T` is not zero-sized and `cap > 0`, `ptr` was allocated by the global allocator with `Layout::array::<T>(cap)`; otherwise, it is dangling
+        ptr: NonNull<T>,
+        cap: usize,
+        // tells the drop checker that the buffer (through its users) owns values of `T`
+        _owns: PhantomData<T>,
+    }
+
+    impl<T> RawBuf<T> {
+        const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+        /// Smallest non-zero capacity, so tiny buffers don't reallocate on every push.
+        const MIN_NON_ZERO_CAP: usize = 4;
+
+        /// Create an empty buffer, without allocating.
+        pub(crate) const fn new() -> Self {
+            Self {
+                ptr: NonNull::dangling(),
+                cap: if Self::IS_ZST { usize::MAX } else { 0 },
+                _owns: PhantomData,
+            }
+        }
+
+        /// Create a buffer with room for exactly `capacity` values (or more, for zero-sized types).
+        ///
+        /// # Panics
+        ///
+        /// If the buffer would be larger than `isize::MAX` bytes.
+        pub(crate) fn with_capacity(capacity: usize) -> Self {
+            let mut buf = Self::new();
+            buf.set_capacity(capacity);
+            buf
+        }
+
+        /// Number of values the buffer has room for.
+        #[inline]
+        pub(crate) const fn capacity(&self) -> usize {
+            self.cap
+        }
+
+        /// Pointer to the start of the buffer, valid for reads and writes of `capacity` values.
+        ///
+        /// The pointer is dangling (but aligned and non-null) if nothing is allocated.
+        #[inline]
+        pub(crate) const fn ptr(&self) -> *mut T {
+            self.ptr.as_ptr()
+        }
+
+        /// Make room for at least `additional` more values after the first `len`, growing geometrically to keep pushes amortized O(1).
+        ///
+        /// The new capacity is the largest of: double the old capacity, exactly enough room, and a small minimum.
+        ///
+        /// # Panics
+        ///
+        /// If the required capacity overflows, or the buffer would be larger than `isize::MAX` bytes.
+        pub(crate) fn reserve(&mut self, len: usize, additional: usize) {
+            handle_reserve(self.try_reserve(len, additional));
+        }
+
+        /// Like [`reserve`](Self::reserve), but returns an error instead of panicking or aborting, leaving the buffer unchanged.
+        pub(crate) fn try_reserve(
+            &mut self,
+            len: usize,
+            additional: usize,
+        ) -> Result<(), TryReserveError> {
+            let required = Self::required_capacity(len, additional)?;
+            if required <= self.cap {
+                return Ok(());
+            }
+            let doubled = self.cap.saturating_mul(2);
+            self.try_set_capacity(required.max(doubled).max(Self::MIN_NON_ZERO_CAP))
+        }
+
+        /// Make room for exactly `additional` more values after the first `len`, without growing any further.
+        ///
+        /// # Panics
+        ///
+        /// If the required capacity overflows, or the buffer would be larger than `isize::MAX` bytes.
+        pub(crate) fn reserve_exact(&mut self, len: usize, additional: usize) {
+            handle_reserve(self.try_reserve_exact(len, additional));
+        }
+
+        /// Like [`reserve_exact`](Self::reserve_exact), but returns an error instead of panicking or aborting, leaving the buffer unchanged.
+        pub(crate) fn try_reserve_exact(
+            &mut self,
+            len: usize,
+            additional: usize,
+        ) -> Result<(), TryReserveError> {
+            let required = Self::required_capacity(len, additional)?;
+            if required <= self.cap {
+                return Ok(());
+            }
+            self.try_set_capacity(required)
+        }
+
+        /// Room needed for `additional` values after the first `len`.
+        ///
+        /// Fails if the result overflows (which, for zero-sized types, means more than `usize::MAX` values).
+        fn required_capacity(len: usize, additional: usize) -> Result<usize, TryReserveError> {
+            len.checked_add(additional)
+                .ok_or(TryReserveError::CapacityOverflow)
+        }
+
+        /// Reallocate the buffer to hold exactly `new_cap` values, keeping the contents of the first `min(cap, new_cap)` slots.
+        ///
+        /// Shrinking to 0 frees the allocation. Zero-sized types are unaffected.
+        ///
+        /// # Panics
+        ///
+        /// If the buffer would be larger than `isize::MAX` bytes, in which case nothing is changed.
+        pub(crate) fn set_capacity(&mut self, new_cap: usize) {
+            handle_reserve(self.try_set_capacity(new_cap));
+        }
+
+        /// Like [`set_capacity`](Self::set_capacity), but returns an error instead of panicking or aborting, leaving the buffer unchanged.
+        pub(crate) fn try_set_capacity(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+            if Self::IS_ZST || new_cap == self.cap {
+                return Ok(());
+            }
+            // find both layouts before touching anything, so an error leaves `self` unchanged
+            let old_layout = Self::array_layout(self.cap)?;
+            let new_layout = Self::array_layout(new_cap)?;
+            let old_ptr = self.ptr.cast::<u8>();
+            let alloc_error = TryReserveError::AllocError { layout: new_layout };
+
+            let new_ptr = match (old_layout.size(), new_layout.size()) {
+                (0, 0) => old_ptr,
+                (_, 0) => {
+                    // SAFETY: the old allocation is not zero-size, and was allocated with `old_layout`
+                    unsafe { alloc::alloc::dealloc(old_ptr.as_ptr(), old_layout) };
+                    NonNull::<T>::dangling().cast::<u8>()
+                }
+                (0, _) => {
+                    // SAFETY: the new buffer is not zero-size
+                    let raw = unsafe { alloc::alloc::alloc(new_layout) };
+                    NonNull::new(raw).ok_or(alloc_error)?
+                }
+                (_, new_size) => {
+                    // SAFETY: the old allocation is not zero-size and was allocated with `old_layout`, and `new_size` is non-zero and fits in `isize` (checked by `array_layout`)
+                    let raw =
+                        unsafe { alloc::alloc::realloc(old_ptr.as_ptr(), old_layout, new_size) };
+                    // on failure, the old allocation is untouched and still owned by `self`
+                    NonNull::new(raw).ok_or(alloc_error)?
+                }
+            };
+            self.ptr = new_ptr.cast::<T>();
+            self.cap = new_cap;
+            Ok(())
+        }
+
+        /// Layout of `cap` values of `T`.
+        ///
+        /// Fails if the buffer would be larger than `isize::MAX` bytes.
+        fn array_layout(cap: usize) -> Result<Layout, TryReserveError> {
+            Layout::array::<T>(cap).map_err(|_| TryReserveError::CapacityOverflow)
+        }
+    }
+
+    /// Turn a failed reservation into the usual panic (for overflow) or abort (for running out of memory).
+    #[track_caller]
+    fn handle_reserve(result: Result<(), TryReserveError>) {
+        match result {
+            Ok(()) => {}
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => alloc::alloc::handle_alloc_error(layout),
+        }
+    }
+
+    /// Translation of destructor.
+    ///
+    /// Only the allocation is freed; any values still in the buffer must already have been dropped (or moved out).
+    impl<T> Drop for RawBuf<T> {
+        fn drop(&mut self) {
+            self.set_capacity(0);
+        }
+    }
+
+    // SAFETY: `RawBuf` is a unique owner of its allocation (like `MyBox<[MaybeUninit<T>]>`), so it is as thread-safe as `T`
+    unsafe impl<T: Send> Send for RawBuf<T> {}
+
+    // SAFETY: `&RawBuf` only gives out a raw pointer, which its users only turn into `&T` when they are themselves shared
+    unsafe impl<T: Sync> Sync for RawBuf<T> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{allocator::TryReserveError, test_alloc::count_allocations};
+
+    #[test]
+    fn new_does_not_allocate() {
+        let (buf, allocations) = count_allocations(RawBuf::<u64>::new);
+        assert_eq!(allocations, 0);
+        assert_eq!(buf.capacity(), 0);
+    }
+
+    #[test]
+    fn reserve_doubles() {
+        let mut buf = RawBuf::<u32>::new();
+        buf.reserve(0, 1);
+        assert_eq!(buf.capacity(), 4);
+        buf.reserve(4, 1);
+        assert_eq!(buf.capacity(), 8);
+        // asking for more than double gives exactly what was asked for
+        buf.reserve(8, 100);
+        assert_eq!(buf.capacity(), 108);
+        // enough room already: nothing changes
+        let ((), allocations) = count_allocations(|| buf.reserve(50, 50));
+        assert_eq!(allocations, 0);
+        assert_eq!(buf.capacity(), 108);
+    }
+
+    #[test]
+    fn set_capacity_and_shrink() {
+        let mut buf = RawBuf::<u32>::new();
+        buf.set_capacity(3);
+        assert_eq!(buf.capacity(), 3);
+        buf.set_capacity(1);
+        assert_eq!(buf.capacity(), 1);
+        buf.set_capacity(0);
+        assert_eq!(buf.capacity(), 0);
+    }
+
+    #[test]
+    fn grow_keeps_contents() {
+        let mut buf = RawBuf::<usize>::with_capacity(2);
+        for i in 0..2 {
+            // SAFETY: `i` is within the capacity
+            #[allow(unsafe_code)]
+            unsafe {
+                buf.ptr().add(i).write(i * 10);
+            }
+        }
+        buf.reserve(2, 1000);
+        // SAFETY: the first two slots were written before growing, and growing keeps them
+        #[allow(unsafe_code)]
+        let kept = unsafe { [buf.ptr().read(), buf.ptr().add(1).read()] };
+        assert_eq!(kept, [0, 10]);
+    }
+
+    #[test]
+    fn reserve_exact_and_try_reserve() {
+        let mut buf = RawBuf::<u32>::new();
+        buf.reserve_exact(0, 3);
+        assert_eq!(buf.capacity(), 3);
+        buf.reserve_exact(3, 1);
+        assert_eq!(buf.capacity(), 4);
+        assert_eq!(buf.try_reserve(4, 1), Ok(()));
+        assert_eq!(buf.capacity(), 8);
+        assert_eq!(
+            buf.try_reserve(8, usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(
+            buf.try_reserve_exact(0, usize::MAX / 2),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(buf.capacity(), 8);
+    }
+
+    #[test]
+    fn zst_never_allocates() {
+        let ((), allocations) = count_allocations(|| {
+            let mut buf = RawBuf::<()>::with_capacity(10);
+            assert_eq!(buf.capacity(), usize::MAX);
+            buf.reserve(usize::MAX - 1, 1);
+            buf.set_capacity(0);
+            assert_eq!(buf.capacity(), usize::MAX);
+        });
+        assert_eq!(allocations, 0);
+    }
+
+    #[test]
+    #[should_panic = "capacity overflow"]
+    fn zst_overflow() {
+        RawBuf::<()>::new().reserve(usize::MAX, 1);
+    }
+
+    #[test]
+    #[should_panic = "capacity overflow"]
+    fn too_large() {
+        RawBuf::<u64>::with_capacity(usize::MAX / 4);
+    }
+}
@@ -0,0 +1,864 @@
+//! Singly linked list data structure.
+//!
+//! Every value lives in its own heap node (a [`MyBox`]), which owns the rest of the list through its `next` link.
+//! This makes pushing and popping at the front O(1), at the cost of O(n) access to anything else.
+
+use crate::{debug_values::DebugValues, my_box::MyBox};
+use core::{cmp::Ordering, fmt, iter::FusedIterator, mem};
+
+/// Owning link to the next node, if any.
+type Link<T> = Option<MyBox<Node<T>>>;
+
+/// A single heap node in the list.
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+/// Singly linked list of values, which can be pushed and popped at the front.
+pub struct MySinglyLinkedList<T> {
+    // invariant: `len` is the number of nodes reachable from `head`
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> MySinglyLinkedList<T> {
+    /// Create an empty list, without allocating.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { head: None, len: 0 }
+    }
+
+    /// Number of values in the list, in O(1).
+    #[doc(alias = "size")]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the list has no values.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add `value` to the front of the list.
+    pub fn push_front(&mut self, value: T) {
+        let next = self.head.take();
+        self.head = Some(MyBox::new(Node { value, next }));
+        self.len += 1;
+    }
+
+    /// Remove the first value from the list and return it, or `None` if it is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.head.take()?.into_inner();
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    /// Get a reference to the first value, or `None` if the list is empty.
+    #[doc(alias = "front")]
+    #[inline]
+    pub fn peek_front(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    /// Get a mutable reference to the first value, or `None` if the list is empty.
+    #[inline]
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+
+    /// Iterate over references to the values, from front to back.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+
+    /// Iterate over mutable references to the values, from front to back.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+            len: self.len,
+        }
+    }
+
+    /// Create a cursor at the front of the list, which can walk it once while inserting and removing values in O(1).
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            link: Some(&mut self.head),
+            index: 0,
+            len: &mut self.len,
+        }
+    }
+
+    /// Split the list in two, returning everything from index `at` onwards (and keeping the first `at` values).
+    ///
+    /// This is O(at), since the list has to be walked to find the split point.
+    ///
+    /// # Panics
+    ///
+    /// If `at > len`.
+    #[track_caller]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len;
+        assert!(
+            at <= len,
+            "`at` split index (is {at}) should be <= len (is {len})"
+        );
+        let mut link = &mut self.head;
+        for _ in 0..at {
+            link = &mut link.as_mut().expect("fewer nodes than `len`").next;
+        }
+        let tail = Self {
+            head: link.take(),
+            len: len - at,
+        };
+        self.len = at;
+        tail
+    }
+
+    /// Reverse the order of the values in O(n), by relinking the nodes rather than moving any values.
+    pub fn reverse(&mut self) {
+        let mut reversed: Link<T> = None;
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = mem::replace(&mut node.next, reversed);
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    /// Sort the values in O(n log n), using a stable merge sort that relinks the nodes rather than moving any values.
+    ///
+    /// If `compare` panics, every value is still in the list, in an unspecified order.
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        self.merge_sort(&mut compare);
+    }
+
+    /// Sort the values by the key that `key` extracts, using a stable merge sort (see [`sort_by`](Self::sort_by)).
+    #[inline]
+    pub fn sort_by_key<K: Ord>(&mut self, mut key: impl FnMut(&T) -> K) {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Recursively sort both halves of the list, then merge them, taking from the front half when values compare
+    /// equal (to keep the sort stable).
+    fn merge_sort(&mut self, compare: &mut impl FnMut(&T, &T) -> Ordering) {
+        if self.len <= 1 {
+            return;
+        }
+        let back = self.split_off(self.len / 2);
+        // the halves are only ever split between the guard's fields, which it joins back into this list when dropped
+        let mut guard = MergeGuard {
+            merged: Self::new(),
+            front: self,
+            back,
+        };
+        guard.front.merge_sort(compare);
+        guard.back.merge_sort(compare);
+
+        let MergeGuard {
+            merged,
+            front,
+            back,
+        } = &mut guard;
+        let mut tail = &mut merged.head;
+        while let (Some(f), Some(b)) = (&front.head, &back.head) {
+            let source = if compare(&b.value, &f.value).is_lt() {
+                &mut *back
+            } else {
+                &mut **front
+            };
+            let mut node = source.head.take().expect("both halves are non-empty");
+            source.head = node.next.take();
+            source.len -= 1;
+            tail = &mut tail.insert(node).next;
+            merged.len += 1;
+        }
+        // at most one of the halves has values left, which are already sorted, so dropping the guard finishes the merge
+    }
+
+    /// The empty link at the end of the list starting at `head`, in O(n).
+    fn tail_link(head: &mut Link<T>) -> &mut Link<T> {
+        let mut link = head;
+        while let Some(node) = link {
+            link = &mut node.next;
+        }
+        link
+    }
+
+    /// Drop every value in the list, freeing every node.
+    pub fn clear(&mut self) {
+        // unlink one node at a time, so that dropping a node never recursively drops the rest of the list
+        let mut link = self.head.take();
+        self.len = 0;
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+/// The pieces of a list in the middle of a merge: the values merged so far, and what's left of each sorted half.
+///
+/// Dropping it joins the pieces back into `front`'s list, in that order, even if a comparison panicked.
+struct MergeGuard<'a, T> {
+    merged: MySinglyLinkedList<T>,
+    front: &'a mut MySinglyLinkedList<T>,
+    back: MySinglyLinkedList<T>,
+}
+
+impl<T> Drop for MergeGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut joined = mem::take(&mut self.merged);
+        for mut rest in [mem::take(self.front), mem::take(&mut self.back)] {
+            let MySinglyLinkedList { head, len } = &mut joined;
+            *MySinglyLinkedList::tail_link(head) = rest.head.take();
+            *len += rest.len;
+        }
+        *self.front = joined;
+    }
+}
+
+/// Translation of destructor.
+///
+/// Nodes are freed one at a time, so even very long lists can't overflow the stack.
+impl<T> Drop for MySinglyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Translation of the default constructor into Rust.
+impl<T> Default for MySinglyLinkedList<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translation of the copy constructor into Rust.
+impl<T: Clone> Clone for MySinglyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+/// Rust-specific helper to visualize the values in a programmer-friendly way, from front to back.
+impl<T: fmt::Debug> fmt::Debug for MySinglyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+/// Translation of `operator==`: lists are equal if they hold equal values in the same order.
+impl<T: PartialEq> PartialEq for MySinglyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for MySinglyLinkedList<T> {}
+
+impl<T: Ord> MySinglyLinkedList<T> {
+    /// Sort the values in ascending order, using a stable merge sort (see [`sort_by`](Self::sort_by)).
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp);
+    }
+}
+
+/// Iterator over references to the values of a [`MySinglyLinkedList`], created by [`MySinglyLinkedList::iter`].
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        self.len -= 1;
+        Some(&node.value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Translation of the copy constructor into Rust.
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            len: self.len,
+        }
+    }
+}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&DebugValues(self.clone()))
+            .finish()
+    }
+}
+
+/// Iterator over mutable references to the values of a [`MySinglyLinkedList`], created by [`MySinglyLinkedList::iter_mut`].
+pub struct IterMut<'a, T> {
+    // each node is only reachable through here once, so the `&mut` to its value never aliases another
+    next: Option<&'a mut Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        // split the node into its value (handed out) and its link (kept to continue from)
+        let Node { value, next } = node;
+        self.next = next.as_deref_mut();
+        self.len -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let iter = Iter {
+            next: self.next.as_deref(),
+            len: self.len,
+        };
+        f.debug_tuple("IterMut").field(&DebugValues(iter)).finish()
+    }
+}
+
+/// Owning iterator over the values of a [`MySinglyLinkedList`], created by [`MySinglyLinkedList::into_iter`](IntoIterator::into_iter).
+///
+/// Any values that weren't yielded are dropped (along with their nodes) when the iterator is dropped.
+pub struct IntoIter<T> {
+    list: MySinglyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Rust-specific helper to visualize the remaining values of the iterator.
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.list).finish()
+    }
+}
+
+/// Cursor over a [`MySinglyLinkedList`], created by [`MySinglyLinkedList::cursor_front_mut`].
+///
+/// The cursor points at a value, or past the end of the list, and can only move forward.
+/// Since every node is only linked from the one before it, the cursor keeps hold of the link to the current node,
+/// which is what allows it to remove the current value.
+pub struct CursorMut<'a, T> {
+    // invariant: always `Some` (it is only taken while moving), holding the link to the current node
+    // (or the empty link at the end), which is node `index` of the list
+    link: Option<&'a mut Link<T>>,
+    index: usize,
+    len: &'a mut usize,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// The link to the current node, or the empty link at the end.
+    fn link(&mut self) -> &mut Link<T> {
+        self.link
+            .as_deref_mut()
+            .expect("cursor link is only taken while moving")
+    }
+
+    /// Position of the current value in the list, or `None` if the cursor is past the end.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        (self.index < *self.len).then_some(self.index)
+    }
+
+    /// Move to the next value (or past the end). Once past the end, this does nothing.
+    pub fn move_next(&mut self) {
+        let link = self
+            .link
+            .take()
+            .expect("cursor link is only taken while moving");
+        self.link = Some(match link {
+            Some(node) => {
+                self.index += 1;
+                &mut node.next
+            }
+            None => link,
+        });
+    }
+
+    /// Get a mutable reference to the current value, or `None` if the cursor is past the end.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.link().as_deref_mut().map(|node| &mut node.value)
+    }
+
+    /// Get a mutable reference to the value after the current one, or `None` if there isn't one.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let node = self.link().as_deref_mut()?;
+        node.next.as_deref_mut().map(|next| &mut next.value)
+    }
+
+    /// Insert `value` right after the current value, without moving the cursor.
+    ///
+    /// If the cursor is past the end, `value` is added to the back of the list, and the cursor stays past the end
+    /// (so inserting repeatedly there appends values in order).
+    pub fn insert_after(&mut self, value: T) {
+        let link = self
+            .link
+            .take()
+            .expect("cursor link is only taken while moving");
+        self.link = Some(match link {
+            Some(node) => {
+                let next = node.next.take();
+                node.next = Some(MyBox::new(Node { value, next }));
+                link
+            }
+            None => {
+                let node = link.insert(MyBox::new(Node { value, next: None }));
+                self.index += 1;
+                &mut node.next
+            }
+        });
+        *self.len += 1;
+    }
+
+    /// Remove the current value and return it, moving the cursor to the value after it.
+    ///
+    /// Returns `None` (and does nothing) if the cursor is past the end.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let link = self.link();
+        let node = link.take()?.into_inner();
+        *link = node.next;
+        *self.len -= 1;
+        Some(node.value)
+    }
+}
+
+/// Rust-specific helper to move the values out of a list, from front to back.
+impl<T> IntoIterator for MySinglyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+/// Rust-specific helper to iterate over a list by reference, as in `for value in &list`.
+impl<'a, T> IntoIterator for &'a MySinglyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Rust-specific helper to iterate over a list by mutable reference, as in `for value in &mut list`.
+impl<'a, T> IntoIterator for &'a mut MySinglyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Rust-specific helper to allow collecting an iterator into a list, keeping the iterator's order.
+impl<T> FromIterator<T> for MySinglyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Rust-specific helper to append every value from an iterator to the back of the list, keeping the iterator's order.
+///
+/// Finding the back of the list is O(n), but it only happens once per call.
+impl<T> Extend<T> for MySinglyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let Self { head, len } = self;
+        let mut tail = Self::tail_link(head);
+        for value in iter {
+            let node = tail.insert(MyBox::new(Node { value, next: None }));
+            tail = &mut node.next;
+            // counted right away, in case the iterator panics before yielding the next value
+            *len += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_drop::{CountDrops, check_drops_once};
+
+    #[test]
+    fn push_pop_order() {
+        let mut list = MySinglyLinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+        for i in 0..5 {
+            list.push_front(i);
+        }
+        assert_eq!(list.len(), 5);
+        assert_eq!(std::format!("{list:?}"), "[4, 3, 2, 1, 0]");
+        // last in, first out
+        for i in (0..5).rev() {
+            assert_eq!(list.peek_front(), Some(&i));
+            assert_eq!(list.pop_front(), Some(i));
+        }
+        assert_eq!(list.peek_front(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn peek_front_mut() {
+        let mut list = MySinglyLinkedList::new();
+        list.push_front(std::string::String::from("a"));
+        list.peek_front_mut().unwrap().push('!');
+        assert_eq!(list.pop_front().as_deref(), Some("a!"));
+        assert_eq!(list.peek_front_mut(), None);
+    }
+
+    #[test]
+    fn clone_keeps_order() {
+        let mut list = MySinglyLinkedList::new();
+        for i in 0..4 {
+            list.push_front(std::format!("{i}"));
+        }
+        let mut clone = list.clone();
+        assert_eq!(clone.len(), 4);
+        assert_eq!(std::format!("{clone:?}"), r#"["3", "2", "1", "0"]"#);
+        // the clone is independent of the original
+        clone.pop_front();
+        clone.peek_front_mut().unwrap().push('!');
+        assert_eq!(std::format!("{list:?}"), r#"["3", "2", "1", "0"]"#);
+        assert_eq!(std::format!("{clone:?}"), r#"["2!", "1", "0"]"#);
+    }
+
+    #[test]
+    fn drops_each_value_once() {
+        let drops = core::cell::Cell::new(0);
+        let popped = check_drops_once(
+            &drops,
+            MySinglyLinkedList::new(),
+            MySinglyLinkedList::push_front,
+            MySinglyLinkedList::pop_front,
+            |list| {
+                list.clear();
+                assert!(list.is_empty());
+            },
+        );
+        // the list is a stack, so the head is the last value pushed
+        assert_eq!(popped, 9);
+    }
+
+    #[test]
+    fn collect_and_iterate() {
+        let mut list: MySinglyLinkedList<_> = (0..5).collect();
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.peek_front(), Some(&0));
+        assert_eq!(
+            list.iter().copied().collect::<std::vec::Vec<_>>(),
+            [0, 1, 2, 3, 4]
+        );
+
+        for value in &mut list {
+            *value *= 10;
+        }
+        list.extend([50, 60]);
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.len(), 6);
+        assert_eq!(std::format!("{iter:?}"), "Iter([10, 20, 30, 40, 50, 60])");
+        assert_eq!(
+            std::format!("{:?}", list.iter_mut()),
+            "IterMut([0, 10, 20, 30, 40, 50, 60])"
+        );
+        assert_eq!(
+            list.into_iter().collect::<std::vec::Vec<_>>(),
+            [0, 10, 20, 30, 40, 50, 60]
+        );
+    }
+
+    #[test]
+    fn equality() {
+        let mut pushed = MySinglyLinkedList::new();
+        for i in (1..=3).rev() {
+            pushed.push_front(i);
+        }
+        let collected: MySinglyLinkedList<_> = [1, 2, 3].into_iter().collect();
+        let mut extended = MySinglyLinkedList::new();
+        extended.push_front(1);
+        extended.extend([2, 3]);
+        assert_eq!(pushed, collected);
+        assert_eq!(collected, extended);
+
+        *extended.peek_front_mut().unwrap() = 0;
+        assert_ne!(pushed, extended);
+        pushed.pop_front();
+        assert_ne!(pushed, collected);
+        assert_eq!(
+            MySinglyLinkedList::<i32>::new(),
+            MySinglyLinkedList::default()
+        );
+    }
+
+    #[test]
+    fn into_iter_drops_rest() {
+        let drops = core::cell::Cell::new(0);
+        let list: MySinglyLinkedList<_> = (0..6).map(|i| CountDrops::new(i, &drops)).collect();
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next().unwrap().value, 0);
+        assert_eq!(drops.get(), 1);
+        assert_eq!(iter.len(), 5);
+        core::mem::drop(iter);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn cursor_remove_and_insert() {
+        let mut list: MySinglyLinkedList<i32> = (0..10).collect();
+        let mut removed = std::vec::Vec::new();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.index(), Some(0));
+        // remove every even value, and put a sentinel after every multiple of 3, in one pass
+        while let Some(&mut value) = cursor.current() {
+            if value % 2 == 0 {
+                removed.push(cursor.remove_current().unwrap());
+                continue;
+            }
+            if value % 3 == 0 {
+                cursor.insert_after(-1);
+                assert_eq!(cursor.peek_next(), Some(&mut -1));
+                cursor.move_next();
+            }
+            cursor.move_next();
+        }
+        assert_eq!(removed, [0, 2, 4, 6, 8]);
+        assert_eq!(list.len(), 7);
+        assert_eq!(
+            list.iter().copied().collect::<std::vec::Vec<_>>(),
+            [1, 3, -1, 5, 7, 9, -1]
+        );
+    }
+
+    #[test]
+    fn cursor_at_end() {
+        let mut list = MySinglyLinkedList::new();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        // inserting past the end appends, in order
+        cursor.insert_after('a');
+        cursor.insert_after('b');
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.iter().collect::<std::string::String>(), "ab");
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(
+            (cursor.index(), cursor.current()),
+            (Some(1), Some(&mut 'b'))
+        );
+        assert_eq!(cursor.remove_current(), Some('b'));
+        assert_eq!(cursor.index(), None);
+        cursor.insert_after('c');
+        assert_eq!(list.iter().collect::<std::string::String>(), "ac");
+    }
+
+    #[test]
+    fn reverse_relinks() {
+        let mut empty = MySinglyLinkedList::<i32>::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+        let mut single: MySinglyLinkedList<_> = [1].into_iter().collect();
+        single.reverse();
+        assert_eq!((single.len(), single.peek_front()), (1, Some(&1)));
+
+        let mut list: MySinglyLinkedList<_> = (0..5).map(|i| std::format!("{i}")).collect();
+        let first = list.peek_front().map(|s| s.as_ptr());
+        list.reverse();
+        assert_eq!(list.len(), 5);
+        assert_eq!(std::format!("{list:?}"), r#"["4", "3", "2", "1", "0"]"#);
+        // the values weren't moved, just relinked
+        assert_eq!(list.iter().last().map(|s| s.as_ptr()), first);
+    }
+
+    #[test]
+    fn sort_non_copy() {
+        let words = ["pear", "fig", "apple", "kiwi", "banana", "date", "cherry"];
+        let mut list: MySinglyLinkedList<std::string::String> =
+            words.iter().map(|&w| w.into()).collect();
+        list.sort();
+        assert_eq!(list.len(), 7);
+        assert_eq!(
+            list.iter()
+                .map(|s| s.as_str())
+                .collect::<std::vec::Vec<_>>(),
+            ["apple", "banana", "cherry", "date", "fig", "kiwi", "pear"]
+        );
+        list.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        assert_eq!(
+            list.iter()
+                .map(|s| s.as_str())
+                .collect::<std::vec::Vec<_>>(),
+            ["banana", "cherry", "apple", "date", "kiwi", "pear", "fig"]
+        );
+        let mut empty = MySinglyLinkedList::<std::string::String>::new();
+        empty.sort();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        // (key, payload) pairs, where the payload records the original order
+        let pairs = [
+            (3, 'a'),
+            (1, 'b'),
+            (3, 'c'),
+            (2, 'd'),
+            (1, 'e'),
+            (3, 'f'),
+            (2, 'g'),
+        ];
+        let mut list: MySinglyLinkedList<_> = pairs.into_iter().collect();
+        list.sort_by_key(|&(key, _)| key);
+        assert_eq!(list.len(), 7);
+        assert_eq!(
+            list.iter()
+                .map(|&(_, payload)| payload)
+                .collect::<std::string::String>(),
+            "bedgacf"
+        );
+
+        // matches the standard library's stable sort on larger inputs
+        let len = if cfg!(miri) { 200 } else { 20_000 };
+        let values: std::vec::Vec<_> = (0..len).map(|i| ((i * 7919) % 101, i)).collect();
+        let mut list: MySinglyLinkedList<_> = values.iter().copied().collect();
+        list.sort_by_key(|&(key, _)| key);
+        let mut expected = values;
+        expected.sort_by_key(|&(key, _)| key);
+        assert!(list.iter().eq(&expected));
+        assert_eq!(list.len(), len);
+    }
+
+    #[test]
+    fn sort_panic_keeps_every_value() {
+        let drops = core::cell::Cell::new(0);
+        let mut list: MySinglyLinkedList<_> =
+            (0..8).rev().map(|i| CountDrops::new(i, &drops)).collect();
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                assert_ne!((a.value, b.value), (2, 3), "comparison panicked");
+                a.value.cmp(&b.value)
+            });
+        }));
+        assert!(result.is_err());
+        // the half-merged nodes were relinked, so nothing was dropped or lost
+        assert_eq!(drops.get(), 0);
+        assert_eq!(list.len(), 8);
+        assert_eq!(list.iter().count(), list.len());
+        list.sort();
+        assert!(list.iter().map(|value| value.value).eq(0..8));
+        drop(list);
+        assert_eq!(drops.get(), 8);
+    }
+
+    #[test]
+    fn extend_with_panicking_iterator() {
+        let mut list: MySinglyLinkedList<_> = (0..2).collect();
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            list.extend((2..10).inspect(|&i| assert_ne!(i, 5, "iterator panicked")));
+        }));
+        assert!(result.is_err());
+        // the values before the panic were linked, and counted
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.iter().count(), list.len());
+        assert!(list.iter().copied().eq(0..5));
+    }
+
+    #[test]
+    fn split_off_at_index() {
+        let mut list: MySinglyLinkedList<_> = (0..5).collect();
+        let back = list.split_off(2);
+        assert_eq!((list.len(), back.len()), (2, 3));
+        assert_eq!(std::format!("{list:?} {back:?}"), "[0, 1] [2, 3, 4]");
+        assert!(list.split_off(2).is_empty());
+        assert_eq!(list.split_off(0).len(), 2);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn long_list_drop() {
+        // dropping nodes recursively would overflow the stack long before this (Miri is too slow for that many nodes)
+        let len = if cfg!(miri) { 1_000 } else { 200_000 };
+        let mut list = MySinglyLinkedList::new();
+        for i in 0..len {
+            list.push_front(i);
+        }
+        assert_eq!(list.len(), len);
+        let clone = list.clone();
+        assert_eq!(clone.peek_front(), Some(&(len - 1)));
+        core::mem::drop(list);
+        core::mem::drop(clone);
+    }
+}
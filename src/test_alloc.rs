@@ -0,0 +1,196 @@
+//! Instrumented allocators for tests.
+//!
+//! All state is thread-local, so tests running in parallel can't observe each other's allocations.
+
+use crate::allocator::{AllocError, Allocator, Global};
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::{Cell, UnsafeCell},
+    mem::MaybeUninit,
+    ptr::NonNull,
+};
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOCATOR: TestAllocator = TestAllocator;
+
+std::thread_local! {
+    /// Allocations larger than this many bytes fail.
+    static FAIL_ABOVE: Cell<usize> = const { Cell::new(usize::MAX) };
+    /// Number of allocations (including reallocations) attempted so far.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    /// Number of deallocations made so far.
+    static DEALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Wrapper around the system allocator that can be told to fail.
+struct TestAllocator;
+
+#[allow(unsafe_code)]
+// SAFETY: every call is either forwarded to `System` unchanged, or fails by returning null, which `GlobalAlloc` allows
+unsafe impl GlobalAlloc for TestAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+        if layout.size() > FAIL_ABOVE.get() {
+            return core::ptr::null_mut();
+        }
+        // SAFETY: the caller upholds `GlobalAlloc::alloc`'s requirements
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+        if layout.size() > FAIL_ABOVE.get() {
+            return core::ptr::null_mut();
+        }
+        // SAFETY: the caller upholds `GlobalAlloc::alloc_zeroed`'s requirements
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        DEALLOCATIONS.set(DEALLOCATIONS.get() + 1);
+        // SAFETY: the caller upholds `GlobalAlloc::dealloc`'s requirements, and `ptr` came from `System`
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+        if new_size > FAIL_ABOVE.get() {
+            return core::ptr::null_mut();
+        }
+        // SAFETY: the caller upholds `GlobalAlloc::realloc`'s requirements, and `ptr` came from `System`
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Run `f`, with every allocation made by the current thread failing until it returns.
+pub fn failing_allocations<R>(f: impl FnOnce() -> R) -> R {
+    failing_allocations_over(0, f)
+}
+
+/// Run `f`, with every allocation of more than `max_size` bytes made by the current thread failing until it returns.
+pub fn failing_allocations_over<R>(max_size: usize, f: impl FnOnce() -> R) -> R {
+    /// Restores normal allocation even if `f` panics.
+    struct Reset(usize);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            FAIL_ABOVE.set(self.0);
+        }
+    }
+
+    let _reset = Reset(FAIL_ABOVE.replace(max_size));
+    f()
+}
+
+/// Run `f`, returning its result along with the number of allocations (including reallocations) the current thread made.
+pub fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.get();
+    let result = f();
+    (result, ALLOCATIONS.get() - before)
+}
+
+/// Run `f`, returning its result along with the number of deallocations the current thread made.
+pub fn count_deallocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = DEALLOCATIONS.get();
+    let result = f();
+    (result, DEALLOCATIONS.get() - before)
+}
+
+/// Fixed-size arena that hands out memory from the front, and only reclaims it when dropped.
+///
+/// It keeps track of how many of its allocations are still live, so tests can check that every allocation is freed exactly once.
+pub struct BumpAllocator<const N: usize = 1024> {
+    buffer: UnsafeCell<[MaybeUninit<u8>; N]>,
+    used: Cell<usize>,
+    live: Cell<usize>,
+}
+
+impl<const N: usize> BumpAllocator<N> {
+    /// Create an empty arena.
+    pub fn new() -> Self {
+        Self {
+            buffer: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            used: Cell::new(0),
+            live: Cell::new(0),
+        }
+    }
+
+    /// Number of allocations that haven't been deallocated yet.
+    pub fn live(&self) -> usize {
+        self.live.get()
+    }
+
+    /// Number of bytes handed out so far (including padding).
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// Check whether `ptr` points into this arena.
+    pub fn contains<T: ?Sized>(&self, ptr: *const T) -> bool {
+        let start = self.buffer.get().addr();
+        (start..start + N).contains(&ptr.addr())
+    }
+}
+
+#[allow(unsafe_code)]
+// SAFETY: blocks never overlap (`used` only grows), and live in `buffer`, which can't move while it is borrowed by `&self`
+unsafe impl<const N: usize> Allocator for &BumpAllocator<N> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let base = self.buffer.get().cast::<u8>();
+        let start = (base.addr() + self.used.get()).next_multiple_of(layout.align()) - base.addr();
+        let end = start + layout.size();
+        if end > N {
+            return Err(AllocError::new(()));
+        }
+        self.used.set(end);
+        self.live.set(self.live.get() + 1);
+        // SAFETY: `start <= end <= N`, so the pointer is within (or one past the end of) `buffer`
+        Ok(unsafe { NonNull::new_unchecked(base.add(start)) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        assert!(
+            self.contains(ptr.as_ptr())
+                && ptr.addr().get() + layout.size() <= self.buffer.get().addr() + N,
+            "deallocated memory from a different allocator"
+        );
+        assert!(self.live.get() > 0, "deallocated more often than allocated");
+        self.live.set(self.live.get() - 1);
+    }
+}
+
+/// Wrapper around the [`Global`] allocator that counts its own allocations and deallocations.
+///
+/// Unlike the thread-local counters above, this only sees memory that is explicitly allocated through it.
+#[derive(Default)]
+pub struct CountingAllocator {
+    allocations: Cell<usize>,
+    deallocations: Cell<usize>,
+}
+
+impl CountingAllocator {
+    /// Number of allocations made so far.
+    pub fn allocations(&self) -> usize {
+        self.allocations.get()
+    }
+
+    /// Number of deallocations made so far.
+    pub fn deallocations(&self) -> usize {
+        self.deallocations.get()
+    }
+}
+
+#[allow(unsafe_code)]
+// SAFETY: every call is forwarded to `Global` unchanged
+unsafe impl Allocator for &CountingAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        self.allocations.set(self.allocations.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.set(self.deallocations.get() + 1);
+        // SAFETY: the caller upholds `deallocate`'s requirements, and `ptr` came from `Global`
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
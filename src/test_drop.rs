@@ -0,0 +1,77 @@
+//! Drop-counting helper for tests.
+
+use core::{cell::Cell, cmp::Ordering};
+
+/// A value that increments a shared counter every time it is dropped.
+///
+/// Used to check that containers drop each element exactly once, and never leak or double-drop.
+#[derive(Debug, Clone)]
+pub struct CountDrops<'a, T = ()> {
+    /// The payload, for tests that care which element is which.
+    pub value: T,
+    drops: &'a Cell<usize>,
+}
+
+impl<'a, T> CountDrops<'a, T> {
+    /// Wrap `value`, counting its drops in `drops`.
+    pub fn new(value: T, drops: &'a Cell<usize>) -> Self {
+        Self { value, drops }
+    }
+}
+
+impl<T> Drop for CountDrops<'_, T> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+/// Compares only the payload, so counted values can be used in ordered containers.
+impl<T: PartialEq> PartialEq for CountDrops<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for CountDrops<'_, T> {}
+
+/// Orders only by the payload, so counted values can be used in ordered containers.
+impl<T: PartialOrd> PartialOrd for CountDrops<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord> Ord for CountDrops<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+/// Check that a container drops each value exactly once, and return the payload of the value that `pop` took out.
+///
+/// Ten values are added with `push`, then one is popped: the caller owns it now, so only dropping it counts. `clear`
+/// has to drop the other nine, and one more value is added to check that dropping the container drops it too.
+pub fn check_drops_once<'a, C>(
+    drops: &'a Cell<usize>,
+    mut container: C,
+    mut push: impl FnMut(&mut C, CountDrops<'a, usize>),
+    pop: impl FnOnce(&mut C) -> Option<CountDrops<'a, usize>>,
+    clear: impl FnOnce(&mut C),
+) -> usize {
+    assert_eq!(drops.get(), 0);
+    for i in 0..10 {
+        push(&mut container, CountDrops::new(i, drops));
+    }
+    let popped = pop(&mut container).expect("the container has values to pop");
+    assert_eq!(drops.get(), 0);
+    let value = popped.value;
+    drop(popped);
+    assert_eq!(drops.get(), 1);
+
+    clear(&mut container);
+    assert_eq!(drops.get(), 10);
+    push(&mut container, CountDrops::new(10, drops));
+    drop(container);
+    assert_eq!(drops.get(), 11);
+    value
+}
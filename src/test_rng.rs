@@ -0,0 +1,25 @@
+//! Pseudo-random numbers for tests, so randomized tests are reproducible.
+
+/// A small linear congruential generator: the same seed always gives the same sequence, which is random enough to
+/// mix up the operations of a test.
+pub struct TestRng(u64);
+
+impl TestRng {
+    /// Start the sequence for `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// The next value in the sequence, in `0..range`.
+    pub fn below(&mut self, range: usize) -> usize {
+        self.0 = self.0.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        // the low bits of an LCG repeat quickly, so use the high ones
+        (self.0 >> 16) as usize % range
+    }
+
+    /// `len` values in `0..range` for `seed`, with plenty of duplicates if `range` is small.
+    pub fn values(seed: u64, len: usize, range: usize) -> impl Iterator<Item = usize> {
+        let mut rng = Self::new(seed);
+        core::iter::repeat_with(move || rng.below(range)).take(len)
+    }
+}
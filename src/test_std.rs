@@ -0,0 +1,6 @@
+//! Conversions into `std` collections for tests, which are easy to compare with literals.
+
+/// Copy values (usually a container's, by reference) into a `Vec`, in iteration order.
+pub fn to_std<'a, T: Clone + 'a>(values: impl IntoIterator<Item = &'a T>) -> std::vec::Vec<T> {
+    values.into_iter().cloned().collect()
+}